@@ -0,0 +1,146 @@
+//! Minimal reader for CSV files, used to import supplementary data (e.g.
+//! PLZ-Gemeinde mappings).
+
+use std::io::BufRead;
+
+use crate::error::Error;
+
+/// Reads a CSV file with a header line. The delimiter (`,` or `;`) is detected
+/// from the header. Quoted fields are supported, but must not contain line
+/// breaks. Lines starting with `#` are comments and are skipped, also before
+/// the header.
+pub(crate) struct CsvReader<R> {
+    reader: R,
+    delimiter: char,
+    header: Vec<String>,
+    line: usize,
+}
+
+impl<R: BufRead> CsvReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, Error> {
+        let mut buf = String::new();
+        let mut line = 0;
+        loop {
+            buf.clear();
+            line += 1;
+            if reader.read_line(&mut buf)? == 0 || !is_comment(&buf) {
+                break;
+            }
+        }
+
+        let header = buf
+            .trim_start_matches('\u{feff}')
+            .trim_end_matches(['\r', '\n']);
+        let delimiter = if header.contains(';') && !header.contains(',') {
+            ';'
+        } else {
+            ','
+        };
+        let header = split_line(header, delimiter)
+            .into_iter()
+            .map(|column| column.trim().to_lowercase())
+            .collect();
+
+        Ok(Self {
+            reader,
+            delimiter,
+            header,
+            line,
+        })
+    }
+
+    /// Returns the index of the first column that has one of the `names`
+    /// (case-insensitive).
+    pub fn column(&self, names: &[&str]) -> Option<usize> {
+        self.header
+            .iter()
+            .position(|column| names.iter().any(|name| column == name))
+    }
+
+    /// Returns the index of a required column, or an error if the column
+    /// doesn't exist.
+    pub fn required_column(&self, names: &[&str]) -> Result<usize, Error> {
+        self.column(names)
+            .ok_or_else(|| self.error(format!("Missing column: {}", names.join(" or "))))
+    }
+
+    /// Reads the next record. Empty lines are skipped.
+    pub fn next_record(&mut self) -> Result<Option<Vec<String>>, Error> {
+        let mut buf = String::new();
+
+        loop {
+            buf.clear();
+            if self.reader.read_line(&mut buf)? == 0 {
+                return Ok(None);
+            }
+            self.line += 1;
+
+            let line = buf.trim_end_matches(['\r', '\n']);
+            if !line.trim().is_empty() && !is_comment(line) {
+                return Ok(Some(split_line(line, self.delimiter)));
+            }
+        }
+    }
+
+    /// Creates an error for the current line.
+    pub fn error(&self, reason: String) -> Error {
+        Error::Csv {
+            line: self.line,
+            reason,
+        }
+    }
+}
+
+fn is_comment(line: &str) -> bool {
+    line.trim_start_matches('\u{feff}').starts_with('#')
+}
+
+fn split_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_splits_quoted_fields() {
+        assert_eq!(
+            split_line(r#"66111,"Saarbrücken, ""Landeshauptstadt""",10041100"#, ','),
+            vec!["66111", r#"Saarbrücken, "Landeshauptstadt""#, "10041100"]
+        );
+        assert_eq!(split_line("a;;b", ';'), vec!["a", "", "b"]);
+    }
+
+    #[test]
+    fn it_skips_comments() {
+        let data =
+            "# Quelle: Die Bundeswahlleiterin\n#\nWKR_NR;WKR_NAME\n# Saarland\n296;Saarbrücken\n";
+        let mut csv = CsvReader::new(data.as_bytes()).unwrap();
+        assert_eq!(csv.column(&["wkr_name"]), Some(1));
+        assert_eq!(
+            csv.next_record().unwrap(),
+            Some(vec!["296".to_owned(), "Saarbrücken".to_owned()])
+        );
+        assert_eq!(csv.line, 5);
+        assert_eq!(csv.next_record().unwrap(), None);
+    }
+}
@@ -1,25 +1,50 @@
 use std::{
+    cmp::Reverse,
     collections::{
         btree_map::{self, BTreeMap},
+        BTreeSet,
         HashMap,
     },
-    io::BufRead,
+    fs::File,
+    io::{BufRead, BufReader},
     iter::Iterator,
     path::Path,
 };
 
 use crate::{
+    csv::CsvReader,
     error::Error,
     model::{
-        datensatz::Datensatz,
+        bezirk::Bezirk,
+        classification::{SettlementType, SizeClass, SizeClassRange, SizeClasses},
+        datensatz::{Datensatz, DatensatzRef, Schluessel},
         gemeinde::{GemeindeDaten, GemeindeSchluessel, RegionalSchluessel},
+        gemeindefrei::GemeindefreiFilter,
         gemeindeverband::{GemeindeverbandDaten, GemeindeverbandSchluessel},
+        gericht::{
+            AmtsgerichtSchluessel,
+            Gericht,
+            LandgerichtSchluessel,
+            OberlandesgerichtSchluessel,
+        },
         kreis::{KreisDaten, KreisSchluessel},
+        kreisebene::Kreisebene,
         land::{LandDaten, LandSchluessel},
+        name::Name,
+        phonetik::koelner_phonetik,
+        plz::{Plz, PlzPrefix},
+        record::{Ebene, Record},
         regierungsbezirk::{RegierungsbezirkDaten, RegierungsbezirkSchluessel},
         region::{RegionDaten, RegionSchluessel},
+        search::{self, FuzzyMatch, FuzzyOptions, MatchKind, SearchOptions, SearchResult},
+        slug::{self, Slugs},
+        stadtstaat::{Stadt, Stadtstaat, STADTSTAATEN},
+        statistics::Statistics,
+        verwaltung::{self, Verwaltung},
+        wahlkreis::{Bundestagswahlkreis, Wahlperiode},
     },
     parser::Parser,
+    query::Query,
 };
 
 /// A (in-memory) database that stores GV100AD data for querying.
@@ -44,6 +69,43 @@ pub struct Database {
     gemeinden: BTreeMap<GemeindeSchluessel, GemeindeDaten>,
 
     gemeindeverband_schluessel: HashMap<RegionalSchluessel, u16>,
+
+    /// Area and population of all units above Gemeinde level, rolled up from
+    /// their Gemeinden
+    totals: HashMap<Schluessel, Statistics>,
+
+    /// Gemeinden by PLZ
+    plz_index: BTreeMap<Plz, Vec<GemeindeSchluessel>>,
+
+    /// Gemeinden by Finanzamtbezirk
+    finanzamt_index: BTreeMap<u16, Vec<GemeindeSchluessel>>,
+
+    /// Gemeinden by Arbeitsagenturbezirk
+    arbeitsagentur_index: BTreeMap<u32, Vec<GemeindeSchluessel>>,
+
+    /// Gemeinden by Amtsgericht
+    amtsgericht_index: BTreeMap<AmtsgerichtSchluessel, Vec<GemeindeSchluessel>>,
+
+    /// Names of courts
+    gericht_names: BTreeMap<Gericht, String>,
+
+    /// Gemeinden by Bundestagswahlkreis
+    wahlkreis_index: BTreeMap<u16, Vec<GemeindeSchluessel>>,
+
+    /// Wahlperiode to which the Wahlkreis numbers belong
+    wahlperiode: Option<Wahlperiode>,
+
+    /// Bundestagswahlkreise by number
+    wahlkreise: BTreeMap<u16, Bundestagswahlkreis>,
+
+    /// Units by folded name
+    name_index: BTreeMap<String, Vec<Schluessel>>,
+
+    /// Units by trigrams of their folded names
+    trigram_index: BTreeMap<String, Vec<Schluessel>>,
+
+    /// Units by Kölner Phonetik code of their names
+    phonetik_index: BTreeMap<String, Vec<Schluessel>>,
 }
 
 impl Database {
@@ -69,6 +131,10 @@ impl Database {
     }
 
     pub fn insert(&mut self, datensatz: Datensatz) {
+        let schluessel = datensatz.schluessel();
+        self.unindex_name(schluessel);
+        self.index_name(schluessel, Record::name(&datensatz));
+
         match datensatz {
             Datensatz::Land(land) => {
                 self.laender.insert(land.schluessel, land);
@@ -88,13 +154,451 @@ impl Database {
                     .insert(gemeindeverband.schluessel, gemeindeverband);
             }
             Datensatz::Gemeinde(gemeinde) => {
+                self.unindex_gemeinde(gemeinde.schluessel);
+                self.index_gemeinde(&gemeinde);
+
                 self.gemeindeverband_schluessel.insert(
                     gemeinde.schluessel.into(),
                     gemeinde.schluessel.gemeindeverband.gemeindeverband,
                 );
-                self.gemeinden.insert(gemeinde.schluessel.into(), gemeinde);
+                self.gemeinden.insert(gemeinde.schluessel, gemeinde);
+            }
+        }
+    }
+
+    /// Adds a Gemeinde to the PLZ and district indices, and to the totals of
+    /// the units it's part of.
+    fn index_gemeinde(&mut self, gemeinde: &GemeindeDaten) {
+        let schluessel = gemeinde.schluessel;
+        let statistics = gemeinde.statistics();
+        for parent in parents_of_gemeinde(schluessel) {
+            *self.totals.entry(parent).or_default() += statistics;
+        }
+        for plz in &gemeinde.all_plz {
+            add_to_index(&mut self.plz_index, *plz, schluessel);
+        }
+        if let Some(finanzamtbezirk) = gemeinde.finanzamtbezirk {
+            add_to_index(&mut self.finanzamt_index, finanzamtbezirk, schluessel);
+        }
+        if let Some(arbeitsagenturbezirk) = gemeinde.arbeitsargenturbezirk {
+            add_to_index(
+                &mut self.arbeitsagentur_index,
+                arbeitsagenturbezirk,
+                schluessel,
+            );
+        }
+        if let Some(gerichtbarkeit) = &gemeinde.gerichtbarkeit {
+            add_to_index(
+                &mut self.amtsgericht_index,
+                gerichtbarkeit.amtsgericht,
+                schluessel,
+            );
+        }
+        for nummer in gemeinde.bundestagswahlkreise.into_iter().flatten() {
+            add_to_index(&mut self.wahlkreis_index, nummer, schluessel);
+        }
+    }
+
+    /// Removes a Gemeinde from the PLZ and district indices, and from the
+    /// totals of the units it's part of.
+    fn unindex_gemeinde(&mut self, schluessel: GemeindeSchluessel) {
+        let gemeinde = match self.gemeinden.get(&schluessel) {
+            Some(gemeinde) => gemeinde,
+            None => return,
+        };
+        let statistics = gemeinde.statistics();
+        for parent in parents_of_gemeinde(schluessel) {
+            if let Some(totals) = self.totals.get_mut(&parent) {
+                *totals -= statistics;
+            }
+        }
+        for plz in &gemeinde.all_plz {
+            remove_from_index(&mut self.plz_index, plz, schluessel);
+        }
+        if let Some(finanzamtbezirk) = &gemeinde.finanzamtbezirk {
+            remove_from_index(&mut self.finanzamt_index, finanzamtbezirk, schluessel);
+        }
+        if let Some(arbeitsagenturbezirk) = &gemeinde.arbeitsargenturbezirk {
+            remove_from_index(
+                &mut self.arbeitsagentur_index,
+                arbeitsagenturbezirk,
+                schluessel,
+            );
+        }
+        if let Some(gerichtbarkeit) = &gemeinde.gerichtbarkeit {
+            remove_from_index(
+                &mut self.amtsgericht_index,
+                &gerichtbarkeit.amtsgericht,
+                schluessel,
+            );
+        }
+        for nummer in gemeinde.bundestagswahlkreise.into_iter().flatten() {
+            remove_from_index(&mut self.wahlkreis_index, &nummer, schluessel);
+        }
+    }
+
+    /// Adds a unit to the name, trigram and phonetic indices.
+    fn index_name(&mut self, schluessel: Schluessel, name: &Name) {
+        for form in search::name_forms(name) {
+            add_to_index(&mut self.name_index, form, schluessel);
+        }
+        for trigram in search::name_trigrams(name) {
+            add_to_index(&mut self.trigram_index, trigram, schluessel);
+        }
+        for code in search::phonetic_forms(name) {
+            add_to_index(&mut self.phonetik_index, code, schluessel);
+        }
+    }
+
+    /// Removes a unit from the name, trigram and phonetic indices.
+    fn unindex_name(&mut self, schluessel: Schluessel) {
+        let name = match self.datensatz(schluessel) {
+            Some(datensatz) => Record::name(&datensatz).clone(),
+            None => return,
+        };
+        for form in &search::name_forms(&name) {
+            remove_from_index(&mut self.name_index, form, schluessel);
+        }
+        for trigram in &search::name_trigrams(&name) {
+            remove_from_index(&mut self.trigram_index, trigram, schluessel);
+        }
+        for code in &search::phonetic_forms(&name) {
+            remove_from_index(&mut self.phonetik_index, code, schluessel);
+        }
+    }
+
+    fn rebuild_plz_index(&mut self) {
+        self.plz_index.clear();
+        for gemeinde in self.gemeinden.values() {
+            for plz in &gemeinde.all_plz {
+                add_to_index(&mut self.plz_index, *plz, gemeinde.schluessel);
+            }
+        }
+    }
+
+    /// Imports a PLZ-Gemeinde mapping from a CSV file at `path`. See
+    /// [`Database::import_plz`].
+    pub fn import_plz_from_path<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<PlzImportReport, Error> {
+        self.import_plz(BufReader::new(File::open(path)?))
+    }
+
+    /// Imports a PLZ-Gemeinde mapping from a CSV file.
+    ///
+    /// The CSV file must have a header and contain the columns `plz` and `ags`
+    /// (Amtlicher Gemeindeschluessel, i.e. Regionalschluessel). Other columns
+    /// (e.g. `ort`) are ignored. Leading zeros that were lost (e.g. `1067`
+    /// instead of `01067`) are restored.
+    ///
+    /// The imported PLZ replace [`GemeindeDaten::all_plz`] for all Gemeinden
+    /// in the file, and are used to look up Gemeinden by PLZ. The PLZ from the
+    /// GV100AD file is always kept. The returned report lists differences
+    /// between the mapping and the GV100AD data.
+    pub fn import_plz<R: BufRead>(&mut self, reader: R) -> Result<PlzImportReport, Error> {
+        let mut csv = CsvReader::new(reader)?;
+        let plz_column = csv.required_column(&["plz", "postleitzahl"])?;
+        let ags_column = csv.required_column(&[
+            "ags",
+            "gemeindeschluessel",
+            "gemeindeschlüssel",
+            "amtlicher gemeindeschlüssel",
+        ])?;
+
+        let mut report = PlzImportReport::default();
+        let mut imported = BTreeMap::<GemeindeSchluessel, BTreeSet<Plz>>::new();
+
+        while let Some(record) = csv.next_record()? {
+            let field = |i: usize| record.get(i).map(|s| s.trim()).unwrap_or_default();
+
+            let plz = pad_zeros(field(plz_column), 5)
+                .parse::<Plz>()
+                .map_err(|e| csv.error(e.to_string()))?;
+
+            let ags = pad_zeros(field(ags_column), 8)
+                .parse::<RegionalSchluessel>()
+                .map_err(|e| csv.error(e.to_string()))?;
+
+            match self.regional_to_gemeinde_schluessel(ags) {
+                Some(schluessel) => {
+                    imported.entry(schluessel).or_default().insert(plz);
+                    report.imported += 1;
+                }
+                None => {
+                    report.unknown_ags.insert(ags);
+                }
+            }
+        }
+
+        for (schluessel, gemeinde) in self.gemeinden.iter_mut() {
+            match imported.remove(schluessel) {
+                Some(mut all_plz) => {
+                    if let Some(plz) = gemeinde.plz {
+                        if all_plz.insert(plz) {
+                            report.missing_register_plz.push(*schluessel);
+                        }
+                    }
+                    if gemeinde.plz_unambiguous && all_plz.len() > 1 {
+                        report.unexpectedly_ambiguous.push(*schluessel);
+                    }
+                    gemeinde.all_plz = all_plz.into_iter().collect();
+                }
+                None => {
+                    if gemeinde.plz.is_some() {
+                        report.not_imported.push(*schluessel);
+                    }
+                }
+            }
+        }
+
+        self.rebuild_plz_index();
+
+        Ok(report)
+    }
+
+    /// Imports names of courts from a CSV file at `path`. See
+    /// [`Database::import_gericht_names`].
+    pub fn import_gericht_names_from_path<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<usize, Error> {
+        self.import_gericht_names(BufReader::new(File::open(path)?))
+    }
+
+    /// Imports names of courts from a CSV file.
+    ///
+    /// The CSV file must have a header and contain the columns `gericht` and
+    /// `name`. `gericht` is the key of an Oberlandesgericht (e.g. `101`),
+    /// Landgericht (e.g. `1011`) or Amtsgericht (e.g. `101109`). Other columns
+    /// are ignored. Returns the number of imported names.
+    pub fn import_gericht_names<R: BufRead>(&mut self, reader: R) -> Result<usize, Error> {
+        let mut csv = CsvReader::new(reader)?;
+        let gericht_column = csv.required_column(&["gericht", "schluessel", "schlüssel"])?;
+        let name_column = csv.required_column(&["name", "bezeichnung"])?;
+
+        let mut imported = 0;
+
+        while let Some(record) = csv.next_record()? {
+            let field = |i: usize| record.get(i).map(|s| s.trim()).unwrap_or_default();
+
+            let gericht = field(gericht_column)
+                .parse::<Gericht>()
+                .map_err(|e| csv.error(e.to_string()))?;
+
+            let name = field(name_column);
+            if name.is_empty() {
+                return Err(csv.error(format!("Missing name for court {}", gericht)));
+            }
+
+            self.gericht_names.insert(gericht, name.to_owned());
+            imported += 1;
+        }
+
+        Ok(imported)
+    }
+
+    /// Returns the name of a court, if it was imported with
+    /// [`Database::import_gericht_names`].
+    pub fn gericht_name<G: Into<Gericht>>(&self, gericht: G) -> Option<&str> {
+        self.gericht_names
+            .get(&gericht.into())
+            .map(|name| name.as_str())
+    }
+
+    /// Returns all Gemeinden in the district of a court. `k` can be the key of
+    /// an Oberlandesgericht, Landgericht or Amtsgericht.
+    pub fn gemeinden_in_gerichtsbezirk<K>(&self, k: K) -> impl Iterator<Item = &GemeindeDaten>
+    where
+        K: IntoRangeKey<AmtsgerichtSchluessel>,
+    {
+        self.amtsgericht_index
+            .range(k.into_range_key())
+            .flat_map(|(_, gemeinden)| gemeinden)
+            .filter_map(move |k| self.gemeinden.get(k))
+    }
+
+    /// Returns all Amtsgerichte that have at least one Gemeinde in their
+    /// district, in ascending order.
+    pub fn amtsgerichte(&self) -> impl Iterator<Item = AmtsgerichtSchluessel> + '_ {
+        self.amtsgericht_index.keys().copied()
+    }
+
+    /// Returns the Wahlperiode to which the Wahlkreis numbers belong, if known.
+    /// This is set with [`Database::set_wahlperiode`] or when importing
+    /// Wahlkreise.
+    pub fn wahlperiode(&self) -> Option<Wahlperiode> {
+        self.wahlperiode
+    }
+
+    /// Sets the Wahlperiode to which the Wahlkreis numbers in the GV100AD file
+    /// belong.
+    pub fn set_wahlperiode(&mut self, wahlperiode: Wahlperiode) {
+        self.wahlperiode = Some(wahlperiode);
+    }
+
+    /// Imports Bundestagswahlkreise from a CSV file at `path`. See
+    /// [`Database::import_wahlkreise`].
+    pub fn import_wahlkreise_from_path<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        wahlperiode: Wahlperiode,
+    ) -> Result<usize, Error> {
+        self.import_wahlkreise(BufReader::new(File::open(path)?), wahlperiode)
+    }
+
+    /// Imports numbers and names of the Bundestagswahlkreise of `wahlperiode`
+    /// from a CSV file, as published by the Bundeswahlleiterin.
+    ///
+    /// The CSV file must have a header and contain the columns `wkr_nr` and
+    /// `wkr_name`. The column `land_nr` is optional. Comment lines starting
+    /// with `#` are skipped. Previously imported Wahlkreise are replaced.
+    /// Returns the number of imported Wahlkreise.
+    ///
+    /// Returns an error if the database already belongs to another
+    /// Wahlperiode, since the numbers would not match.
+    pub fn import_wahlkreise<R: BufRead>(
+        &mut self,
+        reader: R,
+        wahlperiode: Wahlperiode,
+    ) -> Result<usize, Error> {
+        if let Some(expected) = self.wahlperiode {
+            if expected != wahlperiode {
+                return Err(Error::WahlperiodeMismatch {
+                    expected: expected.wahlperiode,
+                    got: wahlperiode.wahlperiode,
+                });
             }
         }
+
+        let mut csv = CsvReader::new(reader)?;
+        let nummer_column = csv.required_column(&["wkr_nr", "wahlkreis", "nummer"])?;
+        let name_column = csv.required_column(&["wkr_name", "wahlkreisname", "name"])?;
+        let land_column = csv.column(&["land_nr", "land"]);
+
+        let mut wahlkreise = BTreeMap::new();
+
+        while let Some(record) = csv.next_record()? {
+            let field = |i: usize| record.get(i).map(|s| s.trim()).unwrap_or_default();
+
+            let nummer = field(nummer_column)
+                .parse::<u16>()
+                .map_err(|e| csv.error(e.to_string()))?;
+
+            let land = land_column
+                .map(field)
+                .filter(|land| !land.is_empty())
+                .map(|land| pad_zeros(land, 2).parse::<LandSchluessel>())
+                .transpose()
+                .map_err(|e| csv.error(e.to_string()))?;
+
+            wahlkreise.insert(
+                nummer,
+                Bundestagswahlkreis {
+                    wahlperiode,
+                    nummer,
+                    name: field(name_column).to_owned(),
+                    land,
+                },
+            );
+        }
+
+        let imported = wahlkreise.len();
+        self.wahlkreise = wahlkreise;
+        self.wahlperiode = Some(wahlperiode);
+
+        Ok(imported)
+    }
+
+    /// Returns a Bundestagswahlkreis by number, if it was imported with
+    /// [`Database::import_wahlkreise`].
+    pub fn wahlkreis(&self, nummer: u16) -> Option<&Bundestagswahlkreis> {
+        self.wahlkreise.get(&nummer)
+    }
+
+    /// Returns all imported Bundestagswahlkreise, ordered by number.
+    pub fn wahlkreise(&self) -> impl Iterator<Item = &Bundestagswahlkreis> {
+        self.wahlkreise.values()
+    }
+
+    /// Returns all Gemeinden that lie (at least partially) in
+    /// Bundestagswahlkreis `nummer`.
+    ///
+    /// Gemeinden that are split over several Wahlkreise (e.g. large cities)
+    /// are returned for each of them. Since the GV100AD file only specifies the
+    /// first and last Wahlkreis of such Gemeinden, they are also returned for
+    /// possible gaps in between.
+    pub fn gemeinden_in_wahlkreis(&self, nummer: u16) -> impl Iterator<Item = &GemeindeDaten> {
+        self.wahlkreis_index
+            .get(&nummer)
+            .into_iter()
+            .flatten()
+            .filter_map(move |k| self.gemeinden.get(k))
+    }
+
+    /// Returns the numbers of all Bundestagswahlkreise that have at least one
+    /// Gemeinde, in ascending order.
+    pub fn wahlkreis_nummern(&self) -> impl Iterator<Item = u16> + '_ {
+        self.wahlkreis_index.keys().copied()
+    }
+
+    /// Returns all Gemeinden in the district of Finanzamt `finanzamt`.
+    pub fn gemeinden_in_finanzamtbezirk(
+        &self,
+        finanzamt: u16,
+    ) -> impl Iterator<Item = &GemeindeDaten> {
+        self.finanzamt_index
+            .get(&finanzamt)
+            .into_iter()
+            .flatten()
+            .filter_map(move |k| self.gemeinden.get(k))
+    }
+
+    /// Returns the numbers of all Finanzamtbezirke that have at least one
+    /// Gemeinde, in ascending order.
+    pub fn finanzamtbezirke(&self) -> impl Iterator<Item = u16> + '_ {
+        self.finanzamt_index.keys().copied()
+    }
+
+    /// Returns all Gemeinden in the district of Agentur für Arbeit
+    /// `arbeitsagentur`.
+    pub fn gemeinden_in_arbeitsagenturbezirk(
+        &self,
+        arbeitsagentur: u32,
+    ) -> impl Iterator<Item = &GemeindeDaten> {
+        self.arbeitsagentur_index
+            .get(&arbeitsagentur)
+            .into_iter()
+            .flatten()
+            .filter_map(move |k| self.gemeinden.get(k))
+    }
+
+    /// Returns the numbers of all Arbeitsagenturbezirke that have at least one
+    /// Gemeinde, in ascending order.
+    pub fn arbeitsagenturbezirke(&self) -> impl Iterator<Item = u32> + '_ {
+        self.arbeitsagentur_index.keys().copied()
+    }
+
+    /// Returns all Gemeinden in a functional district of any kind.
+    pub fn gemeinden_in_bezirk<B: Into<Bezirk>>(
+        &self,
+        bezirk: B,
+    ) -> Box<dyn Iterator<Item = &GemeindeDaten> + '_> {
+        match bezirk.into() {
+            Bezirk::Finanzamt(finanzamt) => Box::new(self.gemeinden_in_finanzamtbezirk(finanzamt)),
+            Bezirk::Arbeitsagentur(arbeitsagentur) => {
+                Box::new(self.gemeinden_in_arbeitsagenturbezirk(arbeitsagentur))
+            }
+            Bezirk::Gericht(gericht) => Box::new(self.gemeinden_in_gerichtsbezirk(gericht)),
+            Bezirk::Bundestagswahlkreis(nummer) => Box::new(self.gemeinden_in_wahlkreis(nummer)),
+        }
+    }
+
+    /// Returns area and population of a functional district, summed over its
+    /// Gemeinden. Gemeinden that are split over several Wahlkreise are counted
+    /// fully in each of them.
+    pub fn bezirk_statistics<B: Into<Bezirk>>(&self, bezirk: B) -> Statistics {
+        self.gemeinden_in_bezirk(bezirk).sum()
     }
 
     pub fn regional_to_gemeinde_schluessel(
@@ -119,13 +623,782 @@ impl Database {
         V::iter_all(self)
     }
 
-    pub fn children<'a, K, V>(&'a self, k: K) -> impl Iterator<Item = &V>
+    pub fn children<'a, K, V>(&'a self, k: K) -> impl Iterator<Item = &'a V>
     where
         V: IterChildrenOf<'a>,
         K: IntoRangeKey<V::Key>,
     {
         V::iter_children_of(self, k).map(|(_, v)| v)
     }
+
+    /// Creates a query over the units of the database. See [`Query`].
+    pub fn query(&self) -> Query<'_> {
+        Query::new(self)
+    }
+
+    /// Searches units by name, for autocompletion. Returns units whose name
+    /// is equal to, or starts with `query`. See [`Database::search_with`].
+    pub fn search(&self, query: &str) -> Vec<SearchResult<'_>> {
+        self.search_with(
+            query,
+            &SearchOptions {
+                prefix: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Searches units by name. The search is case-insensitive, umlauts match
+    /// their transliteration and punctuation is ignored (see
+    /// [`search::fold`]). Names with and without designation and names in
+    /// minority languages are searched.
+    ///
+    /// Results are ranked: exact matches first, then prefix matches, then by
+    /// population.
+    pub fn search_with(&self, query: &str, options: &SearchOptions) -> Vec<SearchResult<'_>> {
+        let query = search::fold(query);
+        if query.is_empty() {
+            return vec![];
+        }
+
+        let mut matches = HashMap::new();
+        if let Some(units) = self.name_index.get(&query) {
+            for schluessel in units {
+                matches.insert(*schluessel, MatchKind::Exact);
+            }
+        }
+        if options.prefix {
+            let prefixed = self
+                .name_index
+                .range(query.clone()..)
+                .take_while(|(name, _)| name.starts_with(&query));
+            for (_, units) in prefixed {
+                for schluessel in units {
+                    matches.entry(*schluessel).or_insert(MatchKind::Prefix);
+                }
+            }
+        }
+
+        let mut results = matches
+            .into_iter()
+            .filter(|(schluessel, _)| {
+                options
+                    .ebene
+                    .map_or(true, |ebene| schluessel.ebene() == ebene)
+                    && options
+                        .within
+                        .map_or(true, |within| within.contains(*schluessel))
+            })
+            .filter_map(|(schluessel, match_kind)| {
+                Some(SearchResult {
+                    datensatz: self.datensatz(schluessel)?,
+                    match_kind,
+                    population: self.totals(schluessel).unwrap_or_default().population_total,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        results.sort_by_key(|result| {
+            (
+                result.match_kind,
+                Reverse(result.population),
+                result.datensatz.schluessel(),
+            )
+        });
+        if let Some(limit) = options.limit {
+            results.truncate(limit);
+        }
+
+        results
+    }
+
+    /// Searches units by name, tolerating typos, different spellings and
+    /// abbreviations (e.g. `Fridrichstal` finds `Friedrichsthal`).
+    ///
+    /// Names are scored by the trigram similarity to the query (see
+    /// [`search::similarity`]). If [`FuzzyOptions::phonetik`] is set, names
+    /// that sound like the query according to the Kölner Phonetik are
+    /// boosted. Candidates are taken from trigram and phonetic indices, so
+    /// the query doesn't scan all units. Results are ordered by descending
+    /// score.
+    pub fn fuzzy_search(&self, query: &str, options: &FuzzyOptions) -> Vec<FuzzyMatch<'_>> {
+        let folded = search::fold(query);
+        if folded.is_empty() {
+            return vec![];
+        }
+        let query_trigrams = search::trigrams(&folded);
+
+        let phonetic = if options.phonetik {
+            let code = koelner_phonetik(&folded);
+            self.phonetik_index.get(&code).cloned().unwrap_or_default()
+        } else {
+            vec![]
+        };
+
+        // Count the shared trigrams of each candidate. With `shared` trigrams in
+        // common, the similarity can't be better than `2 * shared / (n + shared)`,
+        // so most candidates can be skipped without looking at their names.
+        let mut shared = HashMap::<Schluessel, usize>::new();
+        for trigram in &query_trigrams {
+            for schluessel in self.trigram_index.get(trigram).into_iter().flatten() {
+                *shared.entry(*schluessel).or_default() += 1;
+            }
+        }
+        let n = query_trigrams.len() as f64;
+        let candidates = shared
+            .into_iter()
+            .filter(|(_, shared)| 2.0 * *shared as f64 / (n + *shared as f64) >= options.min_score)
+            .map(|(schluessel, _)| schluessel)
+            .chain(phonetic.iter().copied())
+            .collect::<BTreeSet<_>>();
+
+        let mut matches = candidates
+            .into_iter()
+            .filter(|schluessel| {
+                options
+                    .ebene
+                    .map_or(true, |ebene| schluessel.ebene() == ebene)
+                    && options
+                        .within
+                        .map_or(true, |within| within.contains(*schluessel))
+            })
+            .filter_map(|schluessel| {
+                let datensatz = self.datensatz(schluessel)?;
+                let similarity = search::name_forms(Record::name(&datensatz))
+                    .iter()
+                    .map(|form| search::similarity(&folded, form))
+                    .fold(0.0, f64::max);
+                let is_phonetic = phonetic.binary_search(&schluessel).is_ok();
+                let score = if is_phonetic {
+                    (similarity + 1.0) / 2.0
+                } else {
+                    similarity
+                };
+
+                (score >= options.min_score).then(|| FuzzyMatch {
+                    datensatz,
+                    score,
+                    phonetic: is_phonetic,
+                    path: self.hierarchy(schluessel),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        matches.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.datensatz.schluessel().cmp(&b.datensatz.schluessel()))
+        });
+        if let Some(limit) = options.limit {
+            matches.truncate(limit);
+        }
+
+        matches
+    }
+
+    /// Returns the unit with key `k` and its parents, from the Land down to
+    /// the unit (e.g. Land, Regierungsbezirk, Kreis, Gemeindeverband and
+    /// Gemeinde). Levels without a record (e.g. Regierungsbezirke in most
+    /// Laender) are skipped.
+    pub fn hierarchy<K: Into<Schluessel>>(&self, k: K) -> Vec<DatensatzRef<'_>> {
+        let k = k.into();
+
+        let mut keys = vec![Schluessel::from(k.land())];
+        keys.extend(k.regierungsbezirk().map(Schluessel::from));
+        if let Some(kreis) = k.kreis() {
+            keys.push(RegionSchluessel::of_kreis(kreis).into());
+            keys.push(kreis.into());
+        }
+        keys.extend(k.gemeindeverband().map(Schluessel::from));
+        keys.push(k);
+        keys.dedup();
+
+        keys.into_iter().filter_map(|k| self.datensatz(k)).collect()
+    }
+
+    /// Returns the record with key `k`, of any level.
+    pub fn datensatz<K: Into<Schluessel>>(&self, k: K) -> Option<DatensatzRef<'_>> {
+        match k.into() {
+            Schluessel::Land(k) => self.laender.get(&k).map(DatensatzRef::Land),
+            Schluessel::Regierungsbezirk(k) => self
+                .regierungsbezirke
+                .get(&k)
+                .map(DatensatzRef::Regierungsbezirk),
+            Schluessel::Region(k) => self.regionen.get(&k).map(DatensatzRef::Region),
+            Schluessel::Kreis(k) => self.kreise.get(&k).map(DatensatzRef::Kreis),
+            Schluessel::Gemeindeverband(k) => self
+                .gemeindeverbaende
+                .get(&k)
+                .map(DatensatzRef::Gemeindeverband),
+            Schluessel::Gemeinde(k) => self.gemeinden.get(&k).map(DatensatzRef::Gemeinde),
+        }
+    }
+
+    /// Returns all records, level by level from the Laender down to the
+    /// Gemeinden.
+    pub fn datensaetze(&self) -> impl Iterator<Item = DatensatzRef<'_>> {
+        self.laender
+            .values()
+            .map(DatensatzRef::Land)
+            .chain(
+                self.regierungsbezirke
+                    .values()
+                    .map(DatensatzRef::Regierungsbezirk),
+            )
+            .chain(self.regionen.values().map(DatensatzRef::Region))
+            .chain(self.kreise.values().map(DatensatzRef::Kreis))
+            .chain(
+                self.gemeindeverbaende
+                    .values()
+                    .map(DatensatzRef::Gemeindeverband),
+            )
+            .chain(self.gemeinden.values().map(DatensatzRef::Gemeinde))
+    }
+
+    /// Returns all Kreise and kreisfreie Staedte, with their area and
+    /// population.
+    pub fn kreisebene(&self) -> impl Iterator<Item = Kreisebene<'_>> {
+        self.kreise
+            .values()
+            .map(move |kreis| self.kreisebene_of(kreis))
+    }
+
+    /// Returns all Kreise and kreisfreie Staedte in a unit (e.g. a Land), with
+    /// their area and population.
+    pub fn kreisebene_in<K>(&self, k: K) -> impl Iterator<Item = Kreisebene<'_>>
+    where
+        K: IntoRangeKey<KreisSchluessel>,
+    {
+        self.children::<_, KreisDaten>(k)
+            .map(move |kreis| self.kreisebene_of(kreis))
+    }
+
+    fn kreisebene_of<'a>(&'a self, kreis: &'a KreisDaten) -> Kreisebene<'a> {
+        // The Gemeinde of a kreisfreie Stadt is usually kreisfrei (61/62), but
+        // can be a plain Stadt (63). Either way it's the only Gemeinde.
+        let stadt = if kreis.textkennzeichen.is_city() {
+            let mut gemeinden = self.children::<_, GemeindeDaten>(kreis.schluessel);
+            match (gemeinden.next(), gemeinden.next()) {
+                (Some(gemeinde), None) => Some(gemeinde),
+                _ => self
+                    .children::<_, GemeindeDaten>(kreis.schluessel)
+                    .find(|gemeinde| gemeinde.textkennzeichen.is_kreisfrei()),
+            }
+        } else {
+            None
+        };
+
+        Kreisebene {
+            kreis,
+            stadt,
+            statistics: self.totals(kreis.schluessel).unwrap_or_default(),
+        }
+    }
+
+    /// Returns the body that effectively administers the Gemeinde with key `k`:
+    /// its Amt (or other Gemeindeverband), the erfüllende Gemeinde, or the
+    /// Gemeinde itself if it's verbandsfrei.
+    pub fn verwaltung<K>(&self, k: K) -> Option<Verwaltung<'_>>
+    where
+        GemeindeDaten: Lookup<K>,
+    {
+        let gemeinde: &GemeindeDaten = self.get(k)?;
+
+        match self.gemeindeverbaende.get(&gemeinde.schluessel.into()) {
+            Some(gemeindeverband) if !gemeindeverband.textkennzeichen.is_verbandsfrei() => {
+                Some(self.verwaltung_of_gemeindeverband(gemeindeverband))
+            }
+            _ => Some(Verwaltung::Selbst(gemeinde)),
+        }
+    }
+
+    /// Returns all administering bodies in a unit (e.g. a Kreis). Verbandsfreie
+    /// Gemeinden are returned as [`Verwaltung::Selbst`].
+    pub fn verwaltungen<K>(&self, k: K) -> impl Iterator<Item = Verwaltung<'_>>
+    where
+        K: IntoRangeKey<GemeindeverbandSchluessel>,
+    {
+        self.children::<_, GemeindeverbandDaten>(k)
+            .flat_map(move |gemeindeverband| {
+                if gemeindeverband.textkennzeichen.is_verbandsfrei() {
+                    self.children::<_, GemeindeDaten>(gemeindeverband.schluessel)
+                        .map(Verwaltung::Selbst)
+                        .collect()
+                } else {
+                    vec![self.verwaltung_of_gemeindeverband(gemeindeverband)]
+                }
+            })
+    }
+
+    /// Returns all Gemeinden that are administered by `verwaltung`.
+    pub fn verwaltete_gemeinden<'a>(
+        &'a self,
+        verwaltung: &Verwaltung<'a>,
+    ) -> impl Iterator<Item = &'a GemeindeDaten> {
+        let (gemeinde, gemeindeverband) = match verwaltung {
+            Verwaltung::Selbst(gemeinde) => (Some(*gemeinde), None),
+            _ => (None, verwaltung.gemeindeverband()),
+        };
+
+        gemeinde.into_iter().chain(
+            gemeindeverband
+                .into_iter()
+                .flat_map(move |gemeindeverband| {
+                    self.children::<_, GemeindeDaten>(gemeindeverband.schluessel)
+                }),
+        )
+    }
+
+    fn verwaltung_of_gemeindeverband<'a>(
+        &'a self,
+        gemeindeverband: &'a GemeindeverbandDaten,
+    ) -> Verwaltung<'a> {
+        if gemeindeverband.textkennzeichen.is_administered_by_member() {
+            // The erfüllende Gemeinde is the seat of the administration, and usually also
+            // gives the Gemeindeverband its name.
+            let names = [
+                gemeindeverband.sitz_verwaltung.as_deref(),
+                Some(gemeindeverband.name.as_str()),
+            ];
+            let gemeinde = self
+                .children::<_, GemeindeDaten>(gemeindeverband.schluessel)
+                .find(|gemeinde| {
+                    names
+                        .iter()
+                        .flatten()
+                        .any(|name| verwaltung::is_named(gemeinde, name))
+                });
+
+            if let Some(gemeinde) = gemeinde {
+                return Verwaltung::ErfuellendeGemeinde {
+                    gemeindeverband,
+                    gemeinde,
+                };
+            }
+        }
+
+        Verwaltung::Gemeindeverband(gemeindeverband)
+    }
+
+    /// Creates URL slugs for all units. Slugs are unique among siblings:
+    /// Laender among each other, Regierungsbezirke, Regionen and Kreise
+    /// within their Land, and Gemeindeverbaende and Gemeinden within their
+    /// Kreis.
+    pub fn slugs(&self) -> Slugs {
+        let mut slugs = Slugs::default();
+
+        slugs.insert_siblings(
+            self.laender
+                .values()
+                .map(|land| (land.schluessel.into(), &land.name)),
+        );
+
+        for land in self.laender.keys() {
+            slugs.insert_siblings(self.children::<_, RegierungsbezirkDaten>(*land).map(
+                |regierungsbezirk| (regierungsbezirk.schluessel.into(), &regierungsbezirk.name),
+            ));
+            slugs.insert_siblings(
+                self.children::<_, RegionDaten>(*land)
+                    .map(|region| (region.schluessel.into(), &region.name)),
+            );
+            slugs.insert_siblings(
+                self.children::<_, KreisDaten>(*land)
+                    .map(|kreis| (kreis.schluessel.into(), &kreis.name)),
+            );
+        }
+
+        for kreis in self.kreise.keys() {
+            slugs.insert_siblings(
+                self.children::<_, GemeindeverbandDaten>(*kreis)
+                    .map(|gemeindeverband| {
+                        (gemeindeverband.schluessel.into(), &gemeindeverband.name)
+                    }),
+            );
+            slugs.insert_siblings(
+                self.children::<_, GemeindeDaten>(*kreis)
+                    .map(|gemeinde| (gemeinde.schluessel.into(), &gemeinde.name)),
+            );
+        }
+
+        slugs.insert_paths(
+            self.laender
+                .keys()
+                .map(|k| Schluessel::from(*k))
+                .chain(self.kreise.keys().map(|k| Schluessel::from(*k)))
+                .chain(self.gemeinden.keys().map(|k| Schluessel::from(*k))),
+        );
+
+        slugs
+    }
+
+    /// Returns a label for the unit with key `k`, that tells it apart from all
+    /// other units of the same level, e.g. for dropdowns. Designations are
+    /// stripped (see [`Name::short`]).
+    ///
+    /// If other units share the name, just enough context is added: the Land
+    /// (e.g. `Neustadt a.d. Aisch (Bayern)`), the Kreis (e.g. `Neustadt
+    /// (Landkreis Coburg)`), both, or as a last resort the key. The context
+    /// only depends on the units with the same name, so labels are stable
+    /// across releases, unless a unit with the same name is added or removed.
+    pub fn display_name<K: Into<Schluessel>>(&self, k: K) -> Option<String> {
+        let k = k.into();
+        let name = Record::name(&self.datensatz(k)?).short();
+        let folded = search::fold(&name);
+
+        let namesakes = self
+            .name_index
+            .get(&folded)
+            .into_iter()
+            .flatten()
+            .filter(|other| **other != k && other.ebene() == k.ebene())
+            .filter(|other| {
+                self.datensatz(**other).is_some_and(|datensatz| {
+                    search::fold(&Record::name(&datensatz).short()) == folded
+                })
+            })
+            .map(|other| self.display_contexts(*other))
+            .collect::<Vec<_>>();
+        if namesakes.is_empty() {
+            return Some(name);
+        }
+
+        let context = self
+            .display_contexts(k)
+            .iter()
+            .enumerate()
+            .find_map(|(i, context)| {
+                let context = context.as_ref()?;
+                namesakes
+                    .iter()
+                    .all(|other| other[i].as_ref() != Some(context))
+                    .then(|| context.clone())
+            })
+            .unwrap_or_else(|| slug::disambiguator(k));
+
+        Some(format!("{} ({})", name, context))
+    }
+
+    /// Returns the possible contexts for [`Database::display_name`], from
+    /// shortest to longest: Land, Kreis, Kreis and Land, and the key. The Kreis
+    /// is named with its title (e.g. `Landkreis Coburg`), so that a Landkreis
+    /// and a kreisfreie Stadt of the same name can be told apart.
+    fn display_contexts(&self, k: Schluessel) -> [Option<String>; 4] {
+        let short_name = |k: Schluessel| Some(Record::name(&self.datensatz(k)?).short());
+
+        let land = if k.ebene() == Ebene::Land {
+            None
+        } else {
+            short_name(k.land().into())
+        };
+        let kreis = k
+            .kreis()
+            .filter(|kreis| Schluessel::from(*kreis) != k)
+            .and_then(|kreis| self.get::<_, KreisDaten>(kreis))
+            .map(|kreis| kreis.official_name().long());
+        let kreis_and_land = match (&kreis, &land) {
+            (Some(kreis), Some(land)) => Some(format!("{}, {}", kreis, land)),
+            _ => None,
+        };
+
+        [land, kreis, kreis_and_land, Some(slug::disambiguator(k))]
+    }
+
+    /// Returns the Region (only Baden-Wuerttemberg) of a Kreis, Gemeindeverband
+    /// or Gemeinde. Returns `None` outside of Baden-Wuerttemberg.
+    pub fn region_of<K: Into<KreisSchluessel>>(&self, k: K) -> Option<&RegionDaten> {
+        self.get(k.into())
+    }
+
+    /// Whether the unit with key `k` is a city-state (Berlin, Hamburg or
+    /// Bremen), or is part of one.
+    pub fn is_stadtstaat<K: Into<LandSchluessel>>(&self, k: K) -> bool {
+        let land = k.into();
+        land.is_stadtstaat() && self.laender.contains_key(&land)
+    }
+
+    /// Returns the city-state that the unit with key `k` is part of, with all
+    /// records that describe it. Returns `None` if the unit is not in a
+    /// city-state.
+    pub fn stadtstaat<K: Into<LandSchluessel>>(&self, k: K) -> Option<Stadtstaat<'_>> {
+        let land = k.into();
+        if !land.is_stadtstaat() {
+            return None;
+        }
+
+        let staedte = self
+            .children::<_, GemeindeDaten>(land)
+            .map(|gemeinde| Stadt {
+                kreis: self.get(gemeinde.schluessel),
+                gemeindeverband: self.get(gemeinde.schluessel),
+                gemeinde,
+            })
+            .collect();
+
+        Some(Stadtstaat {
+            land: self.laender.get(&land)?,
+            staedte,
+        })
+    }
+
+    /// Returns all city-states.
+    pub fn stadtstaaten(&self) -> impl Iterator<Item = Stadtstaat<'_>> {
+        STADTSTAATEN
+            .iter()
+            .filter_map(move |land| self.stadtstaat(*land))
+    }
+
+    /// Returns all Gemeinden in a unit. Gemeindefreie Gebiete are not
+    /// included.
+    pub fn gemeinden<K>(&self, k: K) -> impl Iterator<Item = &GemeindeDaten>
+    where
+        K: IntoRangeKey<GemeindeSchluessel>,
+    {
+        self.gemeinden_filtered(k, GemeindefreiFilter::Exclude)
+    }
+
+    /// Returns all gemeindefreie Gebiete in a unit.
+    pub fn gemeindefreie_gebiete<K>(&self, k: K) -> impl Iterator<Item = &GemeindeDaten>
+    where
+        K: IntoRangeKey<GemeindeSchluessel>,
+    {
+        self.gemeinden_filtered(k, GemeindefreiFilter::Only)
+    }
+
+    /// Returns all Gemeinden and/or gemeindefreie Gebiete in a unit, depending
+    /// on `filter`.
+    pub fn gemeinden_filtered<K>(
+        &self,
+        k: K,
+        filter: GemeindefreiFilter,
+    ) -> impl Iterator<Item = &GemeindeDaten>
+    where
+        K: IntoRangeKey<GemeindeSchluessel>,
+    {
+        self.children::<_, GemeindeDaten>(k)
+            .filter(move |gemeinde| filter.matches(gemeinde))
+    }
+
+    /// Returns the number of Gemeinden and/or gemeindefreie Gebiete in a unit,
+    /// depending on `filter`. With [`GemeindefreiFilter::Exclude`] this
+    /// matches the official Destatis count of Gemeinden.
+    pub fn count_gemeinden<K>(&self, k: K, filter: GemeindefreiFilter) -> usize
+    where
+        K: IntoRangeKey<GemeindeSchluessel>,
+    {
+        self.gemeinden_filtered(k, filter).count()
+    }
+
+    /// Returns area, population and number of Gemeinden of the unit with key
+    /// `k`. Unlike [`Database::statistics`], this doesn't iterate over the
+    /// Gemeinden: the totals are computed while the database is built, and kept
+    /// up to date when Gemeinden are inserted. Returns `None` if there is no
+    /// such unit.
+    ///
+    /// Like in the official Destatis figures, area and population of
+    /// gemeindefreie Gebiete are included, but they are not counted as
+    /// Gemeinden.
+    pub fn totals<K: Into<Schluessel>>(&self, k: K) -> Option<Statistics> {
+        let k = k.into();
+        match self.datensatz(k)? {
+            DatensatzRef::Gemeinde(gemeinde) => Some(gemeinde.statistics()),
+            _ => Some(self.totals.get(&k).copied().unwrap_or_default()),
+        }
+    }
+
+    /// Returns area and population of a unit, rolled up from its Gemeinden.
+    /// Like in the official Destatis figures, area and population of
+    /// gemeindefreie Gebiete are included, but they are not counted as
+    /// Gemeinden.
+    pub fn statistics<K>(&self, k: K) -> Statistics
+    where
+        K: IntoRangeKey<GemeindeSchluessel>,
+    {
+        self.statistics_filtered(k, GemeindefreiFilter::Include)
+    }
+
+    /// Returns area and population of a unit, rolled up from its Gemeinden
+    /// and/or gemeindefreie Gebiete, depending on `filter`.
+    pub fn statistics_filtered<K>(&self, k: K, filter: GemeindefreiFilter) -> Statistics
+    where
+        K: IntoRangeKey<GemeindeSchluessel>,
+    {
+        self.gemeinden_filtered(k, filter).sum()
+    }
+
+    /// Returns number, area and population of Gemeinden per
+    /// Gemeindegrößenklasse in a unit. Gemeindefreie Gebiete are not counted.
+    pub fn size_classes<K>(&self, k: K) -> BTreeMap<SizeClass, Statistics>
+    where
+        K: IntoRangeKey<GemeindeSchluessel>,
+    {
+        self.classify(k, |gemeinde| Some(gemeinde.size_class()))
+    }
+
+    /// Returns number, area and population of Gemeinden per custom size class
+    /// in a unit. Gemeindefreie Gebiete are not counted.
+    pub fn size_classes_with<K>(
+        &self,
+        k: K,
+        size_classes: &SizeClasses,
+    ) -> BTreeMap<SizeClassRange, Statistics>
+    where
+        K: IntoRangeKey<GemeindeSchluessel>,
+    {
+        self.classify(k, |gemeinde| {
+            Some(size_classes.classify(gemeinde.population_total))
+        })
+    }
+
+    /// Returns number, area and population of Gemeinden per settlement type in
+    /// a unit. Gemeindefreie Gebiete are not counted.
+    pub fn settlement_types<K>(&self, k: K) -> BTreeMap<SettlementType, Statistics>
+    where
+        K: IntoRangeKey<GemeindeSchluessel>,
+    {
+        self.classify(k, GemeindeDaten::settlement_type)
+    }
+
+    /// Returns all Gemeinden with the PLZ `plz`.
+    pub fn gemeinden_by_plz(&self, plz: Plz) -> impl Iterator<Item = &GemeindeDaten> {
+        self.plz_index
+            .get(&plz)
+            .into_iter()
+            .flatten()
+            .filter_map(move |k| self.gemeinden.get(k))
+    }
+
+    /// Returns all Gemeinden with a PLZ starting with `prefix`, together with
+    /// their PLZ. These are ordered by PLZ.
+    pub fn gemeinden_by_plz_prefix(
+        &self,
+        prefix: PlzPrefix,
+    ) -> impl Iterator<Item = (Plz, &GemeindeDaten)> {
+        self.plz_index
+            .range(prefix.first()..=prefix.last())
+            .flat_map(move |(plz, gemeinden)| {
+                gemeinden
+                    .iter()
+                    .filter_map(move |k| self.gemeinden.get(k))
+                    .map(move |gemeinde| (*plz, gemeinde))
+            })
+    }
+
+    /// Returns all PLZ in use, in ascending order.
+    pub fn plz(&self) -> impl Iterator<Item = Plz> + '_ {
+        self.plz_index.keys().copied()
+    }
+
+    /// Whether the PLZ `plz` is used by more than one Gemeinde.
+    pub fn is_plz_ambiguous(&self, plz: Plz) -> bool {
+        self.plz_index
+            .get(&plz)
+            .is_some_and(|gemeinden| gemeinden.len() > 1)
+    }
+
+    /// Returns all PLZ that are used by more than one Gemeinde.
+    pub fn ambiguous_plz(&self) -> impl Iterator<Item = Plz> + '_ {
+        self.plz_index
+            .iter()
+            .filter(|(_, gemeinden)| gemeinden.len() > 1)
+            .map(|(plz, _)| *plz)
+    }
+
+    /// Returns all PLZ areas that span more than one unit of type `K`, together
+    /// with these units. E.g. `db.plz_spanning::<KreisSchluessel>()` returns
+    /// all PLZ that are used in more than one Kreis.
+    pub fn plz_spanning<K>(&self) -> impl Iterator<Item = (Plz, Vec<K>)> + '_
+    where
+        K: From<GemeindeSchluessel> + Ord + 'static,
+    {
+        self.plz_index.iter().filter_map(|(plz, gemeinden)| {
+            let mut units = gemeinden.iter().map(|k| K::from(*k)).collect::<Vec<_>>();
+            units.sort();
+            units.dedup();
+            if units.len() > 1 {
+                Some((*plz, units))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn classify<K, C, F>(&self, k: K, mut f: F) -> BTreeMap<C, Statistics>
+    where
+        K: IntoRangeKey<GemeindeSchluessel>,
+        C: Ord,
+        F: FnMut(&GemeindeDaten) -> Option<C>,
+    {
+        let mut classes = BTreeMap::<C, Statistics>::new();
+
+        for gemeinde in self.children::<_, GemeindeDaten>(k) {
+            if gemeinde.is_gemeindefrei() {
+                continue;
+            }
+            if let Some(class) = f(gemeinde) {
+                *classes.entry(class).or_default() += gemeinde.statistics();
+            }
+        }
+
+        classes
+    }
+}
+
+/// Adds a Gemeinde to an index. The Gemeinden for each key are kept sorted.
+fn add_to_index<K: Ord, S: Ord>(index: &mut BTreeMap<K, Vec<S>>, k: K, schluessel: S) {
+    let units = index.entry(k).or_default();
+    if let Err(i) = units.binary_search(&schluessel) {
+        units.insert(i, schluessel);
+    }
+}
+
+fn remove_from_index<K: Ord, S: Ord>(index: &mut BTreeMap<K, Vec<S>>, k: &K, schluessel: S) {
+    if let Some(units) = index.get_mut(k) {
+        units.retain(|other| *other != schluessel);
+        if units.is_empty() {
+            index.remove(k);
+        }
+    }
+}
+
+/// Returns the keys of all units a Gemeinde can be part of. The Region is only
+/// meaningful in Baden-Wuerttemberg, but it's harmless elsewhere, since there
+/// are no Regionen to look it up.
+fn parents_of_gemeinde(schluessel: GemeindeSchluessel) -> [Schluessel; 5] {
+    let kreis = KreisSchluessel::from(schluessel);
+    [
+        LandSchluessel::from(schluessel).into(),
+        RegierungsbezirkSchluessel::from(schluessel).into(),
+        RegionSchluessel::of_kreis(kreis).into(),
+        kreis.into(),
+        GemeindeverbandSchluessel::from(schluessel).into(),
+    ]
+}
+
+/// Restores leading zeros of numeric fields, that were lost by spreadsheet
+/// software.
+fn pad_zeros(s: &str, len: usize) -> String {
+    format!("{:0>width$}", s, width = len)
+}
+
+/// Result of importing a PLZ-Gemeinde mapping with [`Database::import_plz`].
+#[derive(Clone, Debug, Default)]
+pub struct PlzImportReport {
+    /// Number of imported PLZ-Gemeinde pairs
+    pub imported: usize,
+
+    /// AGS in the mapping, for which there is no Gemeinde
+    pub unknown_ags: BTreeSet<RegionalSchluessel>,
+
+    /// Gemeinden, for which the PLZ from the GV100AD file is not in the
+    /// mapping
+    pub missing_register_plz: Vec<GemeindeSchluessel>,
+
+    /// Gemeinden that have only one PLZ according to the GV100AD file, but
+    /// more than one in the mapping
+    pub unexpectedly_ambiguous: Vec<GemeindeSchluessel>,
+
+    /// Gemeinden with a PLZ, that are not in the mapping
+    pub not_imported: Vec<GemeindeSchluessel>,
 }
 
 use std::ops::RangeInclusive;
@@ -231,6 +1504,35 @@ impl IntoRangeKey<GemeindeSchluessel> for RegierungsbezirkSchluessel {
     }
 }
 
+/// Creates a range of keys to iterate over all Kreise in a Region
+impl IntoRangeKey<KreisSchluessel> for RegionSchluessel {
+    fn into_range_key(self) -> RangeInclusive<KreisSchluessel> {
+        self.first_kreis()..=self.last_kreis()
+    }
+}
+
+/// Creates a range of keys to iterate over all Gemeindeverbaende in a Region
+impl IntoRangeKey<GemeindeverbandSchluessel> for RegionSchluessel {
+    fn into_range_key(self) -> RangeInclusive<GemeindeverbandSchluessel> {
+        GemeindeverbandSchluessel::new(self.first_kreis(), u16::MIN)
+            ..=GemeindeverbandSchluessel::new(self.last_kreis(), u16::MAX)
+    }
+}
+
+/// Creates a range of keys to iterate over all Gemeinden in a Region
+impl IntoRangeKey<GemeindeSchluessel> for RegionSchluessel {
+    fn into_range_key(self) -> RangeInclusive<GemeindeSchluessel> {
+        GemeindeSchluessel::new(
+            GemeindeverbandSchluessel::new(self.first_kreis(), u16::MIN),
+            u16::MIN,
+        )
+            ..=GemeindeSchluessel::new(
+                GemeindeverbandSchluessel::new(self.last_kreis(), u16::MAX),
+                u16::MAX,
+            )
+    }
+}
+
 /// Creates a range of keys to iterate over all Gemeindeverbaende in a Kreis
 impl IntoRangeKey<GemeindeverbandSchluessel> for KreisSchluessel {
     fn into_range_key(self) -> RangeInclusive<GemeindeverbandSchluessel> {
@@ -254,120 +1556,253 @@ impl IntoRangeKey<GemeindeSchluessel> for GemeindeverbandSchluessel {
     }
 }
 
+/// Creates a range of keys to iterate over all Amtsgerichte in the district of
+/// any court
+impl IntoRangeKey<AmtsgerichtSchluessel> for Gericht {
+    fn into_range_key(self) -> RangeInclusive<AmtsgerichtSchluessel> {
+        match self {
+            Self::Oberlandesgericht(k) => k.into_range_key(),
+            Self::Landgericht(k) => k.into_range_key(),
+            Self::Amtsgericht(k) => k.into_range_key(),
+        }
+    }
+}
+
+/// Creates a range of keys to iterate over all Regierungsbezirke in a unit of
+/// any level. For units below a Regierungsbezirk, this is the Regierungsbezirk
+/// they belong to.
+impl IntoRangeKey<RegierungsbezirkSchluessel> for Schluessel {
+    fn into_range_key(self) -> RangeInclusive<RegierungsbezirkSchluessel> {
+        let regierungsbezirk = match self {
+            Self::Land(k) => return k.into_range_key(),
+            Self::Regierungsbezirk(k) => k,
+            Self::Region(k) => k.regierungsbezirk,
+            Self::Kreis(k) => k.regierungsbezirk,
+            Self::Gemeindeverband(k) => k.into(),
+            Self::Gemeinde(k) => k.into(),
+        };
+        regierungsbezirk..=regierungsbezirk
+    }
+}
+
+/// Creates a range of keys to iterate over all Regionen in a unit of any level.
+/// For units below a Region, this is the Region they belong to.
+impl IntoRangeKey<RegionSchluessel> for Schluessel {
+    fn into_range_key(self) -> RangeInclusive<RegionSchluessel> {
+        let region = match self {
+            Self::Land(k) => return k.into_range_key(),
+            Self::Regierungsbezirk(k) => return k.into_range_key(),
+            Self::Region(k) => k,
+            Self::Kreis(k) => RegionSchluessel::of_kreis(k),
+            Self::Gemeindeverband(k) => RegionSchluessel::of_kreis(k.into()),
+            Self::Gemeinde(k) => RegionSchluessel::of_kreis(k.into()),
+        };
+        region..=region
+    }
+}
+
+/// Creates a range of keys to iterate over all Kreise in a unit of any level.
+/// For units below a Kreis, this is the Kreis they belong to.
+impl IntoRangeKey<KreisSchluessel> for Schluessel {
+    fn into_range_key(self) -> RangeInclusive<KreisSchluessel> {
+        let kreis = match self {
+            Self::Land(k) => return k.into_range_key(),
+            Self::Regierungsbezirk(k) => return k.into_range_key(),
+            Self::Region(k) => return k.into_range_key(),
+            Self::Kreis(k) => k,
+            Self::Gemeindeverband(k) => k.into(),
+            Self::Gemeinde(k) => k.into(),
+        };
+        kreis..=kreis
+    }
+}
+
+/// Creates a range of keys to iterate over all Gemeindeverbaende in a unit of
+/// any level. For Gemeinden, this is the Gemeindeverband they belong to.
+impl IntoRangeKey<GemeindeverbandSchluessel> for Schluessel {
+    fn into_range_key(self) -> RangeInclusive<GemeindeverbandSchluessel> {
+        match self {
+            Self::Land(k) => k.into_range_key(),
+            Self::Regierungsbezirk(k) => k.into_range_key(),
+            Self::Region(k) => k.into_range_key(),
+            Self::Kreis(k) => k.into_range_key(),
+            Self::Gemeindeverband(k) => k..=k,
+            Self::Gemeinde(k) => k.gemeindeverband..=k.gemeindeverband,
+        }
+    }
+}
+
+/// Creates a range of keys to iterate over all Gemeinden in a unit of any
+/// level
+impl IntoRangeKey<GemeindeSchluessel> for Schluessel {
+    fn into_range_key(self) -> RangeInclusive<GemeindeSchluessel> {
+        match self {
+            Self::Land(k) => k.into_range_key(),
+            Self::Regierungsbezirk(k) => k.into_range_key(),
+            Self::Region(k) => k.into_range_key(),
+            Self::Kreis(k) => k.into_range_key(),
+            Self::Gemeindeverband(k) => k.into_range_key(),
+            Self::Gemeinde(k) => k..=k,
+        }
+    }
+}
+
+/// Creates a range of keys to iterate over all Amtsgerichte in the district of
+/// an Oberlandesgericht
+impl IntoRangeKey<AmtsgerichtSchluessel> for OberlandesgerichtSchluessel {
+    fn into_range_key(self) -> RangeInclusive<AmtsgerichtSchluessel> {
+        AmtsgerichtSchluessel::new(LandgerichtSchluessel::new(self, u8::MIN), u8::MIN)
+            ..=AmtsgerichtSchluessel::new(LandgerichtSchluessel::new(self, u8::MAX), u8::MAX)
+    }
+}
+
+/// Creates a range of keys to iterate over all Amtsgerichte in the district of
+/// a Landgericht
+impl IntoRangeKey<AmtsgerichtSchluessel> for LandgerichtSchluessel {
+    fn into_range_key(self) -> RangeInclusive<AmtsgerichtSchluessel> {
+        AmtsgerichtSchluessel::new(self, u8::MIN)..=AmtsgerichtSchluessel::new(self, u8::MAX)
+    }
+}
+
+/// Creates a range that only contains the Amtsgericht itself
+impl IntoRangeKey<AmtsgerichtSchluessel> for AmtsgerichtSchluessel {
+    fn into_range_key(self) -> RangeInclusive<AmtsgerichtSchluessel> {
+        self..=self
+    }
+}
+
 pub trait Lookup<K> {
-    fn lookup<'a>(key: K, db: &'a Database) -> Option<&'a Self>;
+    fn lookup(key: K, db: &Database) -> Option<&Self>;
 }
 
 impl Lookup<LandSchluessel> for LandDaten {
-    fn lookup<'a>(key: LandSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: LandSchluessel, db: &Database) -> Option<&Self> {
         db.laender.get(&key)
     }
 }
 
 impl Lookup<RegierungsbezirkSchluessel> for LandDaten {
-    fn lookup<'a>(key: RegierungsbezirkSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: RegierungsbezirkSchluessel, db: &Database) -> Option<&Self> {
         db.laender.get(&key.into())
     }
 }
 
 impl Lookup<RegionSchluessel> for LandDaten {
-    fn lookup<'a>(key: RegionSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: RegionSchluessel, db: &Database) -> Option<&Self> {
         db.laender.get(&key.into())
     }
 }
 
 impl Lookup<KreisSchluessel> for LandDaten {
-    fn lookup<'a>(key: KreisSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: KreisSchluessel, db: &Database) -> Option<&Self> {
         db.laender.get(&key.into())
     }
 }
 
 impl Lookup<GemeindeverbandSchluessel> for LandDaten {
-    fn lookup<'a>(key: GemeindeverbandSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: GemeindeverbandSchluessel, db: &Database) -> Option<&Self> {
         db.laender.get(&key.into())
     }
 }
 
 impl Lookup<GemeindeSchluessel> for LandDaten {
-    fn lookup<'a>(key: GemeindeSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: GemeindeSchluessel, db: &Database) -> Option<&Self> {
         db.laender.get(&key.into())
     }
 }
 
 impl Lookup<RegierungsbezirkSchluessel> for RegierungsbezirkDaten {
-    fn lookup<'a>(key: RegierungsbezirkSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: RegierungsbezirkSchluessel, db: &Database) -> Option<&Self> {
         db.regierungsbezirke.get(&key)
     }
 }
 
 impl Lookup<RegionSchluessel> for RegierungsbezirkDaten {
-    fn lookup<'a>(key: RegionSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: RegionSchluessel, db: &Database) -> Option<&Self> {
         db.regierungsbezirke.get(&key.into())
     }
 }
 
 impl Lookup<KreisSchluessel> for RegierungsbezirkDaten {
-    fn lookup<'a>(key: KreisSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: KreisSchluessel, db: &Database) -> Option<&Self> {
         db.regierungsbezirke.get(&key.into())
     }
 }
 
 impl Lookup<GemeindeverbandSchluessel> for RegierungsbezirkDaten {
-    fn lookup<'a>(key: GemeindeverbandSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: GemeindeverbandSchluessel, db: &Database) -> Option<&Self> {
         db.regierungsbezirke.get(&key.into())
     }
 }
 
 impl Lookup<GemeindeSchluessel> for RegierungsbezirkDaten {
-    fn lookup<'a>(key: GemeindeSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: GemeindeSchluessel, db: &Database) -> Option<&Self> {
         db.regierungsbezirke.get(&key.into())
     }
 }
 
 impl Lookup<RegionSchluessel> for RegionDaten {
-    fn lookup<'a>(key: RegionSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: RegionSchluessel, db: &Database) -> Option<&Self> {
         db.regionen.get(&key)
     }
 }
 
+impl Lookup<KreisSchluessel> for RegionDaten {
+    fn lookup(key: KreisSchluessel, db: &Database) -> Option<&Self> {
+        db.regionen.get(&RegionSchluessel::of_kreis(key))
+    }
+}
+
+impl Lookup<GemeindeverbandSchluessel> for RegionDaten {
+    fn lookup(key: GemeindeverbandSchluessel, db: &Database) -> Option<&Self> {
+        db.regionen.get(&RegionSchluessel::of_kreis(key.into()))
+    }
+}
+
+impl Lookup<GemeindeSchluessel> for RegionDaten {
+    fn lookup(key: GemeindeSchluessel, db: &Database) -> Option<&Self> {
+        db.regionen.get(&RegionSchluessel::of_kreis(key.into()))
+    }
+}
+
 impl Lookup<KreisSchluessel> for KreisDaten {
-    fn lookup<'a>(key: KreisSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: KreisSchluessel, db: &Database) -> Option<&Self> {
         db.kreise.get(&key)
     }
 }
 
 impl Lookup<GemeindeverbandSchluessel> for KreisDaten {
-    fn lookup<'a>(key: GemeindeverbandSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: GemeindeverbandSchluessel, db: &Database) -> Option<&Self> {
         db.kreise.get(&key.into())
     }
 }
 
 impl Lookup<GemeindeSchluessel> for KreisDaten {
-    fn lookup<'a>(key: GemeindeSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: GemeindeSchluessel, db: &Database) -> Option<&Self> {
         db.kreise.get(&key.into())
     }
 }
 
 impl Lookup<GemeindeverbandSchluessel> for GemeindeverbandDaten {
-    fn lookup<'a>(key: GemeindeverbandSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: GemeindeverbandSchluessel, db: &Database) -> Option<&Self> {
         db.gemeindeverbaende.get(&key)
     }
 }
 
 impl Lookup<GemeindeSchluessel> for GemeindeverbandDaten {
-    fn lookup<'a>(key: GemeindeSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: GemeindeSchluessel, db: &Database) -> Option<&Self> {
         db.gemeindeverbaende.get(&key.into())
     }
 }
 
 impl Lookup<GemeindeSchluessel> for GemeindeDaten {
-    fn lookup<'a>(key: GemeindeSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: GemeindeSchluessel, db: &Database) -> Option<&Self> {
         db.gemeinden.get(&key)
     }
 }
 
 impl Lookup<RegionalSchluessel> for GemeindeDaten {
-    fn lookup<'a>(key: RegionalSchluessel, db: &'a Database) -> Option<&'a Self> {
+    fn lookup(key: RegionalSchluessel, db: &Database) -> Option<&Self> {
         let key = db.regional_to_gemeinde_schluessel(key)?;
         db.gemeinden.get(&key)
     }
@@ -485,10 +1920,22 @@ impl<'a> IterChildrenOf<'a> for GemeindeDaten {
 mod tests {
     use std::io::Cursor;
 
-    use crate::model::{
-        gemeinde::GemeindeDaten,
-        kreis::{KreisDaten, KreisSchluessel},
-        land::{LandDaten, LandSchluessel},
+    use crate::{
+        ags,
+        gemeinde,
+        gemeindeverband,
+        kreis,
+        land,
+        model::{
+            gemeinde::{GemeindeDaten, GemeindeTextkennzeichen},
+            gemeindefrei::Gebietsart,
+            kreis::{KreisDaten, KreisSchluessel, KreisTextkennzeichen},
+            land::{LandDaten, LandSchluessel},
+            record::Textkennzeichen,
+            wahlkreis::Bundestagswahlkreise,
+        },
+        query::SortBy,
+        region,
     };
 
     use super::*;
@@ -510,6 +1957,21 @@ mod tests {
         Database::from_reader(Cursor::new(data)).unwrap()
     }
 
+    fn load_bw_testset() -> Database {
+        let data = r#"102021043008          Baden-Württemberg                                 Stuttgart                                         
+2020210430081         Stuttgart                                         Stuttgart                                         
+30202104300811Stuttgart                                         Stuttgart                                         
+30202104300812Heilbronn-Franken                                 Heilbronn                                         
+402021043008111       Stuttgart, Landeshauptstadt                       Stuttgart                                         42
+6020210430081110000000Stuttgart, Landeshauptstadt                                                                         62    000000207350000063591100000314920    70173       
+402021043008115       Böblingen                                         Böblingen                                         44
+6020210430081150030001Böblingen, Stadt                                                                                    67    000000039030000005015000000024974    71032       
+402021043008121       Heilbronn                                         Heilbronn                                         42
+6020210430081210000000Heilbronn, Stadt                                                                                    62    000000099880000012659200000063119    74072       "#;
+
+        Database::from_reader(Cursor::new(data)).unwrap()
+    }
+
     #[test]
     fn get_land_from_landschluessel() {
         let db = load_testset();
@@ -553,6 +2015,18 @@ mod tests {
         assert_eq!(gemeinde.name, "Beckingen");
     }
 
+    #[test]
+    fn get_by_key_literals() {
+        let db = load_testset();
+        let land: &LandDaten = db.get(kreis!("10042")).unwrap();
+        assert_eq!(land.name, "Saarland");
+        let gemeinde: &GemeindeDaten = db.get(gemeinde!("100420111111")).unwrap();
+        assert_eq!(gemeinde.name, "Beckingen");
+        let gemeinde: &GemeindeDaten = db.get(ags!("10042111")).unwrap();
+        assert_eq!(gemeinde.name, "Beckingen");
+        assert_eq!(db.children::<_, GemeindeDaten>(kreis!("10041")).count(), 2);
+    }
+
     #[test]
     fn iter_all_laender() {
         let db = load_testset();
@@ -585,6 +2059,902 @@ mod tests {
         assert_eq!(gemeinden[3].name, "Losheim am See");
     }
 
+    #[test]
+    fn statistics_of_land() {
+        let db = load_testset();
+        let land = db.statistics(LandSchluessel::new(10));
+        let kreis = db.statistics(kreis!("10042"));
+
+        assert_eq!(land.gemeinden, 4);
+        assert_eq!(land.area.hectares(), 32531);
+        assert_eq!(land.population_total, 221288);
+        assert_eq!(land.population_female(), 111564);
+        assert_eq!(kreis.population_total, 30927);
+        assert!((kreis.population_share_of(&land).unwrap() - 0.13976).abs() < 1e-5);
+        assert!((land.population_density().unwrap() - 680.23).abs() < 1e-2);
+        assert!((land.area.square_kilometers() - 325.31).abs() < 1e-9);
+    }
+
+    #[test]
+    fn classify_gemeinden_in_land() {
+        let db = load_testset();
+
+        let size_classes = db.size_classes(LandSchluessel::new(10));
+        assert_eq!(size_classes.len(), 3);
+        assert_eq!(size_classes[&SizeClass::From5000To10000].gemeinden, 1);
+        assert_eq!(size_classes[&SizeClass::From10000To20000].gemeinden, 2);
+        assert_eq!(size_classes[&SizeClass::From100000To200000].gemeinden, 1);
+
+        let settlement_types = db.settlement_types(LandSchluessel::new(10));
+        assert_eq!(settlement_types[&SettlementType::Grossstadt].gemeinden, 1);
+        assert_eq!(settlement_types[&SettlementType::Kleinstadt].gemeinden, 3);
+        assert_eq!(
+            settlement_types[&SettlementType::Kleinstadt].population_total,
+            40914
+        );
+
+        let size_classes =
+            db.size_classes_with(LandSchluessel::new(10), &SizeClasses::new(vec![15_000]));
+        assert_eq!(size_classes.len(), 2);
+        assert_eq!(size_classes.values().next().unwrap().gemeinden, 2);
+    }
+
+    #[test]
+    fn lookup_gemeinden_by_plz() {
+        let mut db = load_testset();
+
+        let gemeinden = db
+            .gemeinden_by_plz("66111".parse().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(gemeinden.len(), 1);
+        assert_eq!(gemeinden[0].name, "Saarbrücken, Landeshauptstadt");
+
+        let gemeinden = db
+            .gemeinden_by_plz_prefix("666".parse().unwrap())
+            .map(|(plz, gemeinde)| (plz.to_string(), gemeinde.name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(gemeinden, vec![("66679".to_owned(), "Losheim am See")]);
+        assert_eq!(db.ambiguous_plz().count(), 0);
+
+        // Move Beckingen to the PLZ of Friedrichsthal, which is in another Kreis.
+        let mut beckingen = db
+            .get::<_, GemeindeDaten>(gemeinde!("100420111111"))
+            .unwrap()
+            .clone();
+        let plz = "66299".parse().unwrap();
+        beckingen.plz = Some(plz);
+        beckingen.all_plz = vec![plz];
+        db.insert(Datensatz::Gemeinde(beckingen));
+
+        assert!(db.is_plz_ambiguous(plz));
+        assert_eq!(db.ambiguous_plz().collect::<Vec<_>>(), vec![plz]);
+        assert_eq!(db.gemeinden_by_plz("66701".parse().unwrap()).count(), 0);
+        assert_eq!(db.plz_spanning::<KreisSchluessel>().count(), 1);
+        assert_eq!(db.plz_spanning::<LandSchluessel>().count(), 0);
+    }
+
+    #[test]
+    fn import_plz_mapping() {
+        let mut db = load_testset();
+
+        let csv = "osm_id,ags,ort,plz\n\
+                   1,10041100,Saarbrücken,66111\n\
+                   1,10041100,Saarbrücken,66113\n\
+                   1,10041100,\"Saarbrücken, Landeshauptstadt\",66115\n\
+                   2,10042111,Beckingen,66701\n\
+                   2,10042111,Beckingen,66702\n\
+                   3,10042112,Losheim am See,66680\n\
+                   4,9162000,München,80331\n";
+        let report = db.import_plz(Cursor::new(csv)).unwrap();
+
+        assert_eq!(report.imported, 6);
+        assert_eq!(
+            report.unknown_ags.into_iter().collect::<Vec<_>>(),
+            vec!["09162000".parse().unwrap()]
+        );
+        assert_eq!(
+            report.missing_register_plz,
+            vec!["100420112112".parse().unwrap()]
+        );
+        assert_eq!(
+            report.unexpectedly_ambiguous,
+            vec![
+                "100420111111".parse().unwrap(),
+                "100420112112".parse().unwrap()
+            ]
+        );
+        assert_eq!(report.not_imported, vec!["100410511511".parse().unwrap()]);
+
+        let saarbruecken = db.get::<_, GemeindeDaten>(ags!("10041100")).unwrap();
+        assert_eq!(saarbruecken.all_plz.len(), 3);
+
+        let gemeinden = db
+            .gemeinden_by_plz("66113".parse().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(gemeinden.len(), 1);
+        assert_eq!(gemeinden[0].name, "Saarbrücken, Landeshauptstadt");
+        assert_eq!(db.gemeinden_by_plz("66679".parse().unwrap()).count(), 1);
+    }
+
+    #[test]
+    fn gemeinden_in_gerichtsbezirk() {
+        let mut db = load_testset();
+
+        let amtsgericht = "101109".parse::<AmtsgerichtSchluessel>().unwrap();
+        let gemeinden = db
+            .gemeinden_in_gerichtsbezirk(amtsgericht)
+            .map(|gemeinde| gemeinde.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            gemeinden,
+            vec!["Saarbrücken, Landeshauptstadt", "Friedrichsthal, Stadt"]
+        );
+        assert_eq!(
+            db.gemeinden_in_gerichtsbezirk(LandgerichtSchluessel::from(amtsgericht))
+                .count(),
+            4
+        );
+        assert_eq!(
+            db.gemeinden_in_gerichtsbezirk("111".parse::<OberlandesgerichtSchluessel>().unwrap())
+                .count(),
+            0
+        );
+        assert_eq!(db.amtsgerichte().count(), 2);
+
+        let csv = "gericht;name\n\
+                   101;Saarländisches Oberlandesgericht\n\
+                   101109;Amtsgericht Saarbrücken\n";
+        assert_eq!(db.import_gericht_names(Cursor::new(csv)).unwrap(), 2);
+        assert_eq!(
+            db.gericht_name(amtsgericht),
+            Some("Amtsgericht Saarbrücken")
+        );
+        assert_eq!(
+            db.gericht_name(OberlandesgerichtSchluessel::from(amtsgericht)),
+            Some("Saarländisches Oberlandesgericht")
+        );
+        assert_eq!(
+            db.gericht_name(LandgerichtSchluessel::from(amtsgericht)),
+            None
+        );
+    }
+
+    #[test]
+    fn gemeinden_in_wahlkreis() {
+        let mut db = load_testset();
+
+        let names = |db: &Database, nummer| {
+            db.gemeinden_in_wahlkreis(nummer)
+                .map(|gemeinde| gemeinde.name.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&db, 297), vec!["Beckingen", "Losheim am See"]);
+        assert_eq!(
+            db.wahlkreis_nummern().collect::<Vec<_>>(),
+            vec![296, 297, 299]
+        );
+
+        // Split Saarbrücken over Wahlkreise 296 and 297.
+        let mut saarbruecken = db
+            .get::<_, GemeindeDaten>(ags!("10041100"))
+            .unwrap()
+            .clone();
+        saarbruecken.bundestagswahlkreise = Some(Bundestagswahlkreise::Range(296, 297));
+        db.insert(Datensatz::Gemeinde(saarbruecken));
+
+        assert_eq!(names(&db, 296), vec!["Saarbrücken, Landeshauptstadt"]);
+        assert_eq!(
+            names(&db, 297),
+            vec![
+                "Saarbrücken, Landeshauptstadt",
+                "Beckingen",
+                "Losheim am See"
+            ]
+        );
+
+        let csv = "# Bundestagswahl 2021\n\
+                   WKR_NR;WKR_NAME;LAND_NR;LAND_NAME\n\
+                   296;Saarbrücken;10;Saarland\n\
+                   297;Saarlouis;10;Saarland\n";
+        let wahlperiode = Wahlperiode::from_election_year(2021).unwrap();
+        assert_eq!(
+            db.import_wahlkreise(Cursor::new(csv), wahlperiode).unwrap(),
+            2
+        );
+        assert_eq!(db.wahlperiode(), Some(wahlperiode));
+        let wahlkreis = db.wahlkreis(297).unwrap();
+        assert_eq!(wahlkreis.name, "Saarlouis");
+        assert_eq!(wahlkreis.land, Some(LandSchluessel::new(10)));
+
+        assert!(db
+            .import_wahlkreise(Cursor::new(csv), Wahlperiode::new(19))
+            .is_err());
+    }
+
+    #[test]
+    fn kreise_and_gemeinden_in_region() {
+        let db = load_bw_testset();
+
+        let kreise = db
+            .children::<_, KreisDaten>(region!("0811"))
+            .map(|kreis| kreis.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(kreise, vec!["Stuttgart, Landeshauptstadt", "Böblingen"]);
+
+        let gemeinden = db
+            .children::<_, GemeindeDaten>(region!("0812"))
+            .map(|gemeinde| gemeinde.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(gemeinden, vec!["Heilbronn, Stadt"]);
+
+        let region = db.region_of(kreis!("08115")).unwrap();
+        assert_eq!(region.schluessel, region!("0811"));
+        assert_eq!(
+            db.region_of(ags!("08121000").kreis).unwrap().name,
+            "Heilbronn-Franken"
+        );
+        assert!(db.region_of(kreis!("10041")).is_none());
+
+        assert_eq!(db.statistics(region!("0811")).population_total, 686061);
+
+        // Regionen of 10 or more can't have Kreise, but their ranges must not
+        // overflow.
+        let tenth = RegionSchluessel::new(region!("0811").regierungsbezirk, 10);
+        assert_eq!(tenth.first_kreis().kreis, 100);
+        assert_eq!(tenth.last_kreis().kreis, 109);
+        let saturated = RegionSchluessel::new(region!("0811").regierungsbezirk, 26);
+        assert_eq!(saturated.first_kreis().kreis, u8::MAX);
+        assert_eq!(saturated.last_kreis().kreis, u8::MAX);
+        for n in [10, 25, 26, u8::MAX].iter() {
+            let empty = RegionSchluessel::new(region!("0811").regierungsbezirk, *n);
+            assert_eq!(db.children::<_, KreisDaten>(empty).count(), 0);
+            assert_eq!(db.children::<_, GemeindeDaten>(empty).count(), 0);
+        }
+
+        let datensatz = Datensatz::Region(region.clone());
+        assert_eq!(datensatz.name(), "Stuttgart");
+        assert_eq!(datensatz.gebietsstand(), &region.gebietsstand);
+    }
+
+    #[test]
+    fn stadtstaaten() {
+        let data = r#"102021043004          Bremen                                            Bremen                                            
+402021043004011       Bremen, Stadt                                     Bremen                                            41
+502021043004011   0000Bremen, Stadt                                                                                       50
+6020210430040110000000Bremen, Stadt                                                                                       61    000000318610000056755900000279025    28195       
+402021043004012       Bremerhaven, Stadt                                Bremerhaven                                       41
+502021043004012   0000Bremerhaven, Stadt                                                                                  50
+6020210430040120000000Bremerhaven, Stadt                                                                                  61    000000093820000011364300000056609    27568       
+102021043011          Berlin                                            Berlin                                            
+402021043011000       Berlin, Stadt                                     Berlin                                            41
+502021043011000   0000Berlin, Stadt                                                                                       50
+6020210430110000000000Berlin, Stadt                                                                                       61    000000891120000366408800001800595    10178       "#;
+        let db = Database::from_reader(Cursor::new(data)).unwrap();
+
+        assert!(db.is_stadtstaat(land!("11")));
+        assert!(db.is_stadtstaat(kreis!("04012")));
+        assert!(!db.is_stadtstaat(land!("02")));
+        assert!(!load_testset().is_stadtstaat(land!("10")));
+
+        let stadtstaaten = db.stadtstaaten().collect::<Vec<_>>();
+        assert_eq!(stadtstaaten.len(), 2);
+
+        let bremen = &stadtstaaten[0];
+        assert_eq!(bremen.name(), "Bremen");
+        assert!(!bremen.is_single_city());
+        assert_eq!(
+            bremen.kreis_schluessel().collect::<Vec<_>>(),
+            vec![kreis!("04011"), kreis!("04012")]
+        );
+        assert_eq!(bremen.statistics().population_total, 681202);
+
+        let berlin = db.stadtstaat(gemeinde!("110000000000")).unwrap();
+        assert!(berlin.is_single_city());
+        let stadt = &berlin.staedte[0];
+        assert_eq!(stadt.kreis.unwrap().schluessel, kreis!("11000"));
+        assert_eq!(
+            stadt.gemeindeverband.unwrap().schluessel,
+            gemeindeverband!("110000000")
+        );
+        assert_eq!(stadt.gemeinde_schluessel(), gemeinde!("110000000000"));
+        assert!(berlin.contains(stadt.kreis_schluessel()));
+
+        // Each city-state is counted once.
+        let total = db
+            .stadtstaaten()
+            .map(|s| s.statistics())
+            .sum::<Statistics>();
+        assert_eq!(total, db.all::<GemeindeDaten>().sum());
+        assert_eq!(total.gemeinden, 3);
+    }
+
+    #[test]
+    fn gemeindefreie_gebiete() {
+        let mut db = load_testset();
+
+        // Turn Losheim am See into a gemeindefreies Gebiet
+        let mut losheim = db
+            .get::<_, GemeindeDaten>(ags!("10042112"))
+            .unwrap()
+            .clone();
+        losheim.textkennzeichen = GemeindeTextkennzeichen::GemeindefreiesGebietUnbewohnt;
+        db.insert(Datensatz::Gemeinde(losheim));
+
+        let land = land!("10");
+        assert_eq!(db.gemeinden(land).count(), 3);
+        assert_eq!(
+            db.gemeindefreie_gebiete(land)
+                .map(|gemeinde| gemeinde.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Losheim am See"]
+        );
+        assert_eq!(db.count_gemeinden(land, GemeindefreiFilter::Exclude), 3);
+        assert_eq!(db.count_gemeinden(land, GemeindefreiFilter::Include), 4);
+
+        let statistics = db.statistics(land);
+        assert_eq!(statistics.gemeinden, 3);
+        assert_eq!(statistics.gemeindefreie_gebiete, 1);
+        assert_eq!(statistics.population_total, 221288);
+
+        let statistics = db.statistics_filtered(land, GemeindefreiFilter::Exclude);
+        assert_eq!(statistics.gemeindefreie_gebiete, 0);
+        assert_eq!(statistics.population_total, 221288 - 16038);
+
+        let gebiet = db
+            .get::<_, GemeindeDaten>(ags!("10042112"))
+            .unwrap()
+            .gemeindefreies_gebiet()
+            .unwrap();
+        assert!(!gebiet.bewohnt);
+        assert_eq!(gebiet.art, Gebietsart::Gewaesser);
+    }
+
+    #[test]
+    fn verwaltung_of_gemeinden() {
+        let data = r#"402021043001051       Dithmarschen                                      Heide                                             44
+502021043001051   5101Amt Burg-St. Michaelisdonn                        Burg (Dithmarschen)                               51
+6020210430010510115101Buchholz                                                                                            64    000000010530000000108200000000544    25712       
+6020210430010510185101Burg (Dithmarschen)                                                                                 64    000000016300000000415700000002025    25712       
+502021043001051   0044Heide, Stadt                                                                                        50
+6020210430010510440044Heide, Stadt                                                                                        63    000000021990000002168000000010448    25746       
+402021043016075       Saale-Orla-Kreis                                  Schleiz                                           44
+502021043016075   5058Triptis, Stadt                                    Triptis                                           58
+6020210430160750225058Dreitzsch                                                                                           64    000000005950000000028300000000141    07819       
+6020210430160751055058Triptis, Stadt                                                                                      63    000000027270000000369600000001812    07819       "#;
+        let db = Database::from_reader(Cursor::new(data)).unwrap();
+
+        let verwaltung = db.verwaltung(ags!("01051011")).unwrap();
+        assert_eq!(verwaltung.name(), "Amt Burg-St. Michaelisdonn");
+        assert_eq!(verwaltung.sitz_verwaltung(), Some("Burg (Dithmarschen)"));
+        assert!(verwaltung.gemeinde().is_none());
+        assert_eq!(
+            db.verwaltete_gemeinden(&verwaltung)
+                .map(|gemeinde| gemeinde.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Buchholz", "Burg (Dithmarschen)"]
+        );
+
+        let verwaltung = db.verwaltung(ags!("01051044")).unwrap();
+        assert!(matches!(verwaltung, Verwaltung::Selbst(_)));
+        assert_eq!(verwaltung.sitz_verwaltung(), Some("Heide"));
+        assert_eq!(db.verwaltete_gemeinden(&verwaltung).count(), 1);
+
+        let verwaltung = db.verwaltung(ags!("16075022")).unwrap();
+        assert_eq!(verwaltung.name(), "Triptis, Stadt");
+        assert_eq!(
+            verwaltung.gemeinde().unwrap().schluessel,
+            gemeinde!("160755058105")
+        );
+        assert_eq!(verwaltung.gemeindeverband().unwrap().name, "Triptis, Stadt");
+        assert_eq!(db.verwaltete_gemeinden(&verwaltung).count(), 2);
+
+        let verwaltungen = db
+            .verwaltungen(kreis!("01051"))
+            .map(|verwaltung| verwaltung.name().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            verwaltungen,
+            vec!["Heide, Stadt", "Amt Burg-St. Michaelisdonn"]
+        );
+    }
+
+    #[test]
+    fn iter_kreisebene() {
+        let db = load_bw_testset();
+
+        let kreisebene = db.kreisebene().collect::<Vec<_>>();
+        assert_eq!(kreisebene.len(), 3);
+
+        let stuttgart = &kreisebene[0];
+        assert!(stuttgart.is_kreisfreie_stadt());
+        assert_eq!(
+            stuttgart.textkennzeichen(),
+            KreisTextkennzeichen::Stadtkreis
+        );
+        assert_eq!(
+            stuttgart.stadt.unwrap().schluessel,
+            gemeinde!("081110000000")
+        );
+        assert_eq!(stuttgart.statistics.population_total, 635911);
+
+        let boeblingen = &kreisebene[1];
+        assert_eq!(boeblingen.name(), "Böblingen");
+        assert!(!boeblingen.is_kreisfreie_stadt());
+        assert!(boeblingen.stadt.is_none());
+        assert_eq!(boeblingen.statistics.gemeinden, 1);
+
+        assert_eq!(db.kreisebene_in(region!("0812")).count(), 1);
+        assert_eq!(load_testset().kreisebene_in(land!("10")).count(), 2);
+    }
+
+    #[test]
+    fn kreisebene_of_city_with_stadt_gemeinde() {
+        // The Gemeinde of a kreisfreie Stadt can be a plain Stadt (63).
+        let data = r#"102021043007          Rheinland-Pfalz                                   Mainz                                             
+402021043007311       Frankenthal (Pfalz), Stadt                        Frankenthal (Pfalz)                               41
+502021043007311   0000Frankenthal (Pfalz), Stadt                                                                          50
+6020210430073110000000Frankenthal (Pfalz), Stadt                                                                          63    000000093820000011364300000056609    27568       "#;
+        let db = Database::from_reader(Cursor::new(data)).unwrap();
+
+        let kreisebene = db.kreisebene().collect::<Vec<_>>();
+        assert_eq!(kreisebene.len(), 1);
+        assert!(kreisebene[0].is_kreisfreie_stadt());
+
+        let stadt = kreisebene[0].stadt.unwrap();
+        assert_eq!(stadt.schluessel, gemeinde!("073110000000"));
+        assert_eq!(stadt.textkennzeichen, GemeindeTextkennzeichen::Stadt);
+    }
+
+    #[test]
+    fn records_of_all_levels() {
+        let db = load_testset();
+
+        let datensatz = db.datensatz(gemeinde!("100410511511")).unwrap();
+        assert_eq!(datensatz.ebene(), Ebene::Gemeinde);
+        assert_eq!(datensatz.name(), "Friedrichsthal, Stadt");
+        assert_eq!(datensatz.sitz_verwaltung(), None);
+        assert_eq!(
+            datensatz.textkennzeichen(),
+            Some(Textkennzeichen::Gemeinde(GemeindeTextkennzeichen::Stadt))
+        );
+
+        let datensatz = db.datensatz(kreis!("10042")).unwrap();
+        assert_eq!(datensatz.schluessel(), kreis!("10042").into());
+        assert_eq!(datensatz.sitz_verwaltung(), Some("Merzig, Kreisstadt"));
+        assert!(!datensatz.textkennzeichen().unwrap().is_city());
+
+        let land = db.datensatz(land!("10")).unwrap().to_datensatz();
+        assert_eq!(land.ebene(), Ebene::Land);
+        assert_eq!(land.name(), "Saarland");
+        assert_eq!(
+            land.sitz_verwaltung(),
+            Some("Saarbrücken, Landeshauptstadt")
+        );
+        assert!(db.datensatz(land!("09")).is_none());
+
+        let name = db.datensatz(kreis!("10041")).unwrap().name();
+        let gebietsstand = db.datensatz(kreis!("10041")).unwrap().gebietsstand();
+        assert_eq!(name, "Regionalverband Saarbrücken");
+        assert_eq!(gebietsstand, land.gebietsstand());
+        assert_eq!(db.datensatz(land!("10")).unwrap().name(), land.name());
+
+        let ebenen = db
+            .datensaetze()
+            .map(|datensatz| datensatz.ebene())
+            .collect::<Vec<_>>();
+        assert_eq!(ebenen.len(), 12);
+        assert!(ebenen.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn search_by_name() {
+        let mut db = load_testset();
+
+        let results = db.search("saarbrücken");
+        assert_eq!(results.len(), 3);
+        assert!(results
+            .iter()
+            .all(|result| result.match_kind == MatchKind::Exact));
+        assert_eq!(results[0].datensatz.schluessel(), kreis!("10041").into());
+        assert_eq!(results[0].population, 190361);
+
+        let results = db.search_with(
+            "SAARBRUECKEN",
+            &SearchOptions {
+                ebene: Some(Ebene::Gemeinde),
+                ..Default::default()
+            },
+        );
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].datensatz.schluessel(),
+            gemeinde!("100410100100").into()
+        );
+
+        let results = db.search_with(
+            "lo",
+            &SearchOptions {
+                prefix: true,
+                ebene: Some(Ebene::Gemeinde),
+                ..Default::default()
+            },
+        );
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].match_kind, MatchKind::Prefix);
+        assert_eq!(results[0].datensatz.name(), "Losheim am See");
+
+        let results = db.search_with(
+            "saarbruecken",
+            &SearchOptions {
+                prefix: true,
+                within: Some(kreis!("10042").into()),
+                ..Default::default()
+            },
+        );
+        assert!(results.is_empty());
+        assert_eq!(
+            db.search_with(
+                "S",
+                &SearchOptions {
+                    prefix: true,
+                    limit: Some(2),
+                    ..Default::default()
+                }
+            )
+            .len(),
+            2
+        );
+        assert!(db.search(" . ").is_empty());
+
+        let mut gemeinde = db
+            .get::<_, GemeindeDaten>(gemeinde!("100420111111"))
+            .unwrap()
+            .clone();
+        gemeinde.name = Name::parse("St. Beckingen");
+        db.insert(Datensatz::Gemeinde(gemeinde));
+
+        assert_eq!(db.search("Sankt Beckingen").len(), 1);
+        assert_eq!(db.search("Beckingen").len(), 1);
+        assert_eq!(
+            db.search("Beckingen")[0].datensatz.ebene(),
+            Ebene::Gemeindeverband
+        );
+    }
+
+    #[test]
+    fn fuzzy_search_by_name() {
+        let db = load_testset();
+
+        let matches = db.fuzzy_search(
+            "Fridrichstal",
+            &FuzzyOptions {
+                ebene: Some(Ebene::Gemeinde),
+                ..Default::default()
+            },
+        );
+        assert_eq!(matches.len(), 1);
+        assert!(matches[0].phonetic);
+        assert_eq!(
+            matches[0].datensatz.schluessel(),
+            gemeinde!("100410511511").into()
+        );
+        assert_eq!(
+            matches[0]
+                .path
+                .iter()
+                .map(|datensatz| datensatz.name())
+                .collect::<Vec<_>>(),
+            vec![
+                "Saarland",
+                "Regionalverband Saarbrücken",
+                "Friedrichsthal, Stadt",
+                "Friedrichsthal, Stadt"
+            ]
+        );
+
+        let matches = db.fuzzy_search("Losheim a. See", &FuzzyOptions::default());
+        assert_eq!(matches[0].datensatz.name(), "Losheim am See");
+        assert!(matches[0].score > 0.8);
+
+        let matches = db.fuzzy_search(
+            "Saarbruecken",
+            &FuzzyOptions {
+                within: Some(land!("10").into()),
+                limit: Some(1),
+                ..Default::default()
+            },
+        );
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].score, 1.0);
+
+        let strict = FuzzyOptions {
+            min_score: 0.95,
+            phonetik: false,
+            ..Default::default()
+        };
+        assert!(db.fuzzy_search("Fridrichstal", &strict).is_empty());
+        assert!(db
+            .fuzzy_search("Hamburg", &FuzzyOptions::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn display_names_of_namesakes() {
+        let mut db = load_testset();
+        assert_eq!(
+            db.display_name(gemeinde!("100410100100")).unwrap(),
+            "Saarbrücken"
+        );
+        assert_eq!(
+            db.display_name(kreis!("10041")).unwrap(),
+            "Regionalverband Saarbrücken"
+        );
+
+        let friedrichsthal = db
+            .get::<_, GemeindeDaten>(gemeinde!("100410511511"))
+            .unwrap()
+            .clone();
+        for schluessel in [gemeinde!("100420111111"), gemeinde!("110000000000")] {
+            let mut gemeinde = friedrichsthal.clone();
+            gemeinde.schluessel = schluessel;
+            db.insert(Datensatz::Gemeinde(gemeinde));
+        }
+
+        assert_eq!(
+            db.display_name(gemeinde!("100410511511")).unwrap(),
+            "Friedrichsthal (Regionalverband Saarbrücken)"
+        );
+        assert_eq!(
+            db.display_name(gemeinde!("100420111111")).unwrap(),
+            "Friedrichsthal (Landkreis Merzig-Wadern)"
+        );
+        assert_eq!(
+            db.display_name(gemeinde!("110000000000")).unwrap(),
+            "Friedrichsthal (Berlin)"
+        );
+        assert_eq!(
+            db.display_name(gemeinde!("100420112112")).unwrap(),
+            "Losheim am See"
+        );
+        assert!(db.display_name(gemeinde!("100420113113")).is_none());
+    }
+
+    #[test]
+    fn display_names_of_namesakes_in_kreise_of_the_same_name() {
+        // A kreisfreie Stadt (41) and a Landkreis (44) can share their name.
+        let data = r#"102021043009          Bayern                                            München, Landeshauptstadt                                                                                                                           
+402021043009463       Coburg                                            Coburg                                            41                                                                                                
+502021043009463   0000Neustadt                                                                                            50                                                                                                
+6020210430094630000000Neustadt                                                                                            64    000000051850000001488900000007315    66701       1020110455523297                           
+402021043009473       Coburg                                            Coburg                                            44                                                                                                
+502021043009473   0148Neustadt                                                                                            50                                                                                                
+6020210430094731480148Neustadt                                                                                            64    000000051850000001488900000007315    66701       1020110455523297"#;
+        let db = Database::from_reader(Cursor::new(data)).unwrap();
+
+        assert_eq!(
+            db.display_name(gemeinde!("094630000000")).unwrap(),
+            "Neustadt (Kreisfreie Stadt Coburg)"
+        );
+        assert_eq!(
+            db.display_name(gemeinde!("094730148148")).unwrap(),
+            "Neustadt (Landkreis Coburg)"
+        );
+    }
+
+    #[test]
+    fn gemeinden_by_bezirk() {
+        let db = load_testset();
+
+        assert_eq!(
+            db.finanzamtbezirke().collect::<Vec<_>>(),
+            vec![1020, 1040, 1070]
+        );
+        assert_eq!(
+            db.gemeinden_in_finanzamtbezirk(1020)
+                .map(|gemeinde| gemeinde.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Beckingen", "Losheim am See"]
+        );
+        assert_eq!(
+            db.bezirk_statistics(Bezirk::Finanzamt(1020))
+                .population_total,
+            30927
+        );
+        assert_eq!(db.gemeinden_in_finanzamtbezirk(9999).count(), 0);
+
+        assert_eq!(db.arbeitsagenturbezirke().count(), 4);
+        assert_eq!(
+            db.gemeinden_in_arbeitsagenturbezirk(55501)
+                .next()
+                .unwrap()
+                .schluessel,
+            gemeinde!("100410100100")
+        );
+
+        let landgericht = "1011".parse::<LandgerichtSchluessel>().unwrap();
+        assert_eq!(
+            db.bezirk_statistics(landgericht).population_total,
+            db.statistics(land!("10")).population_total
+        );
+        assert_eq!(
+            db.gemeinden_in_bezirk(Bezirk::Bundestagswahlkreis(297))
+                .count(),
+            2
+        );
+        assert_eq!(Bezirk::Finanzamt(1020).to_string(), "Finanzamtbezirk 1020");
+    }
+
+    #[test]
+    fn cached_totals() {
+        let mut db = load_testset();
+
+        for datensatz in db.datensaetze() {
+            let schluessel = datensatz.schluessel();
+            assert_eq!(
+                db.totals(schluessel).unwrap(),
+                db.statistics(schluessel),
+                "{}",
+                schluessel
+            );
+        }
+        assert_eq!(db.totals(land!("10")).unwrap().population_total, 221288);
+        assert_eq!(db.totals(land!("11")).unwrap(), Statistics::default());
+        assert!(db.totals(kreis!("10043")).is_none());
+
+        let mut losheim = db
+            .get::<_, GemeindeDaten>(gemeinde!("100420112112"))
+            .unwrap()
+            .clone();
+        losheim.population_total += 1000;
+        db.insert(Datensatz::Gemeinde(losheim.clone()));
+
+        losheim.schluessel = gemeinde!("100420112113");
+        db.insert(Datensatz::Gemeinde(losheim));
+
+        let kreis = db.totals(kreis!("10042")).unwrap();
+        assert_eq!(kreis, db.statistics(kreis!("10042")));
+        assert_eq!(kreis.population_total, 14889 + 2 * 17038);
+        assert_eq!(kreis.gemeinden, 3);
+        assert_eq!(
+            db.totals(land!("10")).unwrap().population_total,
+            221288 + 1000 + 17038
+        );
+
+        let db = load_bw_testset();
+        assert_eq!(db.totals(region!("0811")).unwrap().population_total, 686061);
+    }
+
+    #[test]
+    fn query_builder() {
+        let db = load_testset();
+
+        let names = |query: Query| {
+            query
+                .records()
+                .iter()
+                .map(|datensatz| datensatz.name().to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(db.query().count(), 4);
+        assert_eq!(
+            names(
+                db.query()
+                    .within(land!("10"))
+                    .population(15_000..)
+                    .sort_by(SortBy::Population)
+                    .descending()
+            ),
+            vec!["Saarbrücken, Landeshauptstadt", "Losheim am See"]
+        );
+        assert_eq!(
+            names(db.query().staedte()),
+            vec!["Saarbrücken, Landeshauptstadt", "Friedrichsthal, Stadt"]
+        );
+        assert_eq!(
+            names(db.query().name("*thal")),
+            vec!["Friedrichsthal, Stadt"]
+        );
+        assert_eq!(
+            names(db.query().density(1100.0..)),
+            vec!["Friedrichsthal, Stadt"]
+        );
+        assert_eq!(
+            db.query()
+                .within(kreis!("10042"))
+                .sort_by(SortBy::Name)
+                .descending()
+                .offset(1)
+                .limit(1)
+                .keys(),
+            vec![Schluessel::from(gemeinde!("100420111111"))]
+        );
+        assert_eq!(
+            db.query().within(kreis!("10042")).descending().keys(),
+            vec![
+                Schluessel::from(gemeinde!("100420112112")),
+                gemeinde!("100420111111").into()
+            ]
+        );
+
+        assert_eq!(
+            db.query()
+                .ebene(Ebene::Kreis)
+                .sort_by(SortBy::Population)
+                .keys(),
+            vec![kreis!("10042").into(), kreis!("10041").into()]
+        );
+        assert_eq!(
+            db.query()
+                .ebene(Ebene::Kreis)
+                .bezirk(Bezirk::Finanzamt(1020))
+                .keys(),
+            vec![kreis!("10042").into()]
+        );
+        assert_eq!(
+            db.query()
+                .ebene(Ebene::Kreis)
+                .within(gemeinde!("100420111111"))
+                .count(),
+            0
+        );
+        assert_eq!(
+            db.query().ebene(Ebene::Land).population(1..).keys(),
+            vec![land!("10").into()]
+        );
+
+        let db = load_bw_testset();
+        assert_eq!(
+            db.query()
+                .ebene(Ebene::Region)
+                .within(land!("08"))
+                .sort_by(SortBy::Population)
+                .descending()
+                .keys(),
+            vec![region!("0811").into(), region!("0812").into()]
+        );
+    }
+
+    #[test]
+    fn slugs_and_paths() {
+        let mut db = load_testset();
+        let slugs = db.slugs();
+
+        assert_eq!(slugs.slug(land!("10")), Some("saarland"));
+        assert_eq!(
+            slugs.path(gemeinde!("100410511511")),
+            Some("saarland/regionalverband-saarbruecken/friedrichsthal")
+        );
+        assert_eq!(slugs.path(kreis!("10042")), Some("saarland/merzig-wadern"));
+        assert_eq!(slugs.path(gemeindeverband!("100410511")), None);
+        assert_eq!(
+            slugs.lookup("/Saarland/Regionalverband-Saarbruecken/friedrichsthal/"),
+            Some(gemeinde!("100410511511").into())
+        );
+        assert_eq!(
+            slugs.lookup("saarland/merzig-wadern"),
+            Some(kreis!("10042").into())
+        );
+        assert_eq!(slugs.lookup("saarland/neustadt"), None);
+
+        for schluessel in [gemeinde!("100420111111"), gemeinde!("100420112112")] {
+            let mut gemeinde = db.get::<_, GemeindeDaten>(schluessel).unwrap().clone();
+            gemeinde.name = Name::parse("Neustadt, Stadt");
+            db.insert(Datensatz::Gemeinde(gemeinde));
+        }
+
+        let slugs = db.slugs();
+        assert_eq!(
+            slugs.path(gemeinde!("100420111111")),
+            Some("saarland/merzig-wadern/neustadt-10042111")
+        );
+        assert_eq!(
+            slugs.lookup("saarland/merzig-wadern/neustadt-10042112"),
+            Some(gemeinde!("100420112112").into())
+        );
+        assert_eq!(slugs.slug(gemeinde!("100410100100")), Some("saarbruecken"));
+    }
+
     #[test]
     fn iter_gemeinden_in_kreis() {
         let db = load_testset();
@@ -15,10 +15,35 @@ pub enum Error {
     #[error("Invalid type: {0}")]
     InvalidType(u8),
 
+    /// An invalid date was read.
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+
+    /// An invalid PLZ was read.
+    #[error("Invalid PLZ: {0}")]
+    InvalidPlz(String),
+
+    /// An invalid court district was read.
+    #[error("Invalid Gerichtbarkeit: {0}")]
+    InvalidGerichtbarkeit(String),
+
+    /// Invalid Bundestagswahlkreise were read.
+    #[error("Invalid Bundestagswahlkreise: {0}")]
+    InvalidBundestagswahlkreise(String),
+
+    /// Wahlkreise of another Wahlperiode than the one of the database were
+    /// imported.
+    #[error("Wahlperiode mismatch: Expected {expected}, but got {got}")]
+    WahlperiodeMismatch { expected: u8, got: u8 },
+
     /// A invalid "Textkennzeichen" was read.
     #[error("Invalid Textkennzeichen: {0}")]
     InvalidTextkennzeichen(u8),
 
+    /// A supplementary CSV file is invalid.
+    #[error("CSV error in line {line}: {reason}")]
+    Csv { line: usize, reason: String },
+
     /// Invalid Regionalschluessel
     #[error("Invalid Regionalschluessel: {0}")]
     ParseKey(#[from] ParseKeyError),
@@ -34,6 +59,8 @@ pub enum ParseKeyError {
     },
     #[error("Keys must be numeric: {0}")]
     NonNumeric(String),
+    #[error("Invalid packed key: {0:#018x}")]
+    InvalidPacked(u64),
 }
 
 impl ParseKeyError {
@@ -16,12 +16,13 @@
 //! This example lists all municipalities of the state *Saarland* with
 //! population:
 //!
-//! ```rust
+//! ```rust,no_run
 //! use gv100ad::{
+//!     land,
 //!     model::{
 //!         gemeinde::GemeindeDaten,
 //!         kreis::KreisDaten,
-//!         land::{LandDaten, LandSchluessel},
+//!         land::LandDaten,
 //!     },
 //!     Database,
 //! };
@@ -29,8 +30,8 @@
 //! // Open the database. Refer to the `README.md` file for the source of the datasets.
 //! let db = Database::from_path("GV100AD3004/GV100AD_300421.txt").unwrap();
 //!
-//! // Parse a key for the state of Saarland
-//! let schluessel = "10".parse::<LandSchluessel>().unwrap();
+//! // Key for the state of Saarland. Key literals are checked at compile time.
+//! let schluessel = land!("10");
 //!
 //! // Get the record for the state of Saarland
 //! let land = db.get::<_, LandDaten>(schluessel).unwrap();
@@ -76,10 +77,13 @@
 //!
 //!  If you think a translation is incorrect or missing, please open an issue.
 
+mod csv;
 pub mod db;
 pub mod error;
+mod macros;
 pub mod model;
 pub mod parser;
+pub mod query;
 
 pub use db::Database;
 pub use parser::Parser;
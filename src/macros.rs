@@ -0,0 +1,168 @@
+//! Macros to create keys from string literals, which are validated at compile
+//! time.
+
+/// Panics, if `s` doesn't have `len` bytes. In const context this is a compile
+/// error.
+pub(crate) const fn check_literal_length(s: &str, len: usize) {
+    if s.len() != len {
+        panic!("Key literal has invalid length");
+    }
+}
+
+/// Parses the digits `s[start..end]`. Panics, if they're not all ASCII digits.
+/// In const context this is a compile error.
+pub(crate) const fn literal_digits(s: &str, start: usize, end: usize) -> u16 {
+    let bytes = s.as_bytes();
+    let mut n = 0;
+    let mut i = start;
+
+    while i < end {
+        let c = bytes[i];
+        if !c.is_ascii_digit() {
+            panic!("Key literals must be numeric");
+        }
+        n = n * 10 + (c - b'0') as u16;
+        i += 1;
+    }
+
+    n
+}
+
+/// Creates a [`LandSchluessel`](crate::model::land::LandSchluessel) from a
+/// literal, e.g. `land!("10")`.
+#[macro_export]
+macro_rules! land {
+    ($s:literal) => {{
+        const KEY: $crate::model::land::LandSchluessel =
+            $crate::model::land::LandSchluessel::from_literal($s);
+        KEY
+    }};
+}
+
+/// Creates a
+/// [`RegierungsbezirkSchluessel`](crate::model::regierungsbezirk::RegierungsbezirkSchluessel)
+/// from a literal, e.g. `regierungsbezirk!("100")`.
+#[macro_export]
+macro_rules! regierungsbezirk {
+    ($s:literal) => {{
+        const KEY: $crate::model::regierungsbezirk::RegierungsbezirkSchluessel =
+            $crate::model::regierungsbezirk::RegierungsbezirkSchluessel::from_literal($s);
+        KEY
+    }};
+}
+
+/// Creates a [`RegionSchluessel`](crate::model::region::RegionSchluessel) from
+/// a literal, e.g. `region!("0811")`.
+#[macro_export]
+macro_rules! region {
+    ($s:literal) => {{
+        const KEY: $crate::model::region::RegionSchluessel =
+            $crate::model::region::RegionSchluessel::from_literal($s);
+        KEY
+    }};
+}
+
+/// Creates a [`KreisSchluessel`](crate::model::kreis::KreisSchluessel) from a
+/// literal, e.g. `kreis!("10041")`.
+///
+/// The literal is validated at compile time, and the macro can be used to
+/// declare constants:
+///
+/// ```rust
+/// use gv100ad::{ags, gemeinde, kreis, model::kreis::KreisSchluessel};
+///
+/// const SAARBRUECKEN: KreisSchluessel = kreis!("10041");
+///
+/// let gemeinde = gemeinde!("100410100100");
+/// assert_eq!(KreisSchluessel::from(gemeinde), SAARBRUECKEN);
+/// assert_eq!(ags!("10041100"), gemeinde.into());
+/// ```
+///
+/// Invalid literals don't compile:
+///
+/// ```rust,compile_fail
+/// let kreis = gv100ad::kreis!("1004");
+/// ```
+///
+/// ```rust,compile_fail
+/// let kreis = gv100ad::kreis!("1004a");
+/// ```
+#[macro_export]
+macro_rules! kreis {
+    ($s:literal) => {{
+        const KEY: $crate::model::kreis::KreisSchluessel =
+            $crate::model::kreis::KreisSchluessel::from_literal($s);
+        KEY
+    }};
+}
+
+/// Creates a
+/// [`GemeindeverbandSchluessel`](crate::model::gemeindeverband::GemeindeverbandSchluessel)
+/// from a literal, e.g. `gemeindeverband!("100410100")`.
+#[macro_export]
+macro_rules! gemeindeverband {
+    ($s:literal) => {{
+        const KEY: $crate::model::gemeindeverband::GemeindeverbandSchluessel =
+            $crate::model::gemeindeverband::GemeindeverbandSchluessel::from_literal($s);
+        KEY
+    }};
+}
+
+/// Creates a [`GemeindeSchluessel`](crate::model::gemeinde::GemeindeSchluessel)
+/// (ARS) from a literal, e.g. `gemeinde!("100410100100")`.
+#[macro_export]
+macro_rules! gemeinde {
+    ($s:literal) => {{
+        const KEY: $crate::model::gemeinde::GemeindeSchluessel =
+            $crate::model::gemeinde::GemeindeSchluessel::from_literal($s);
+        KEY
+    }};
+}
+
+/// Creates a [`RegionalSchluessel`](crate::model::gemeinde::RegionalSchluessel)
+/// (AGS) from a literal, e.g. `ags!("10041100")`.
+#[macro_export]
+macro_rules! ags {
+    ($s:literal) => {{
+        const KEY: $crate::model::gemeinde::RegionalSchluessel =
+            $crate::model::gemeinde::RegionalSchluessel::from_literal($s);
+        KEY
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{
+        gemeinde::{GemeindeSchluessel, RegionalSchluessel},
+        gemeindeverband::GemeindeverbandSchluessel,
+        kreis::KreisSchluessel,
+        region::RegionSchluessel,
+    };
+
+    #[test]
+    fn literals_match_parsed_keys() {
+        assert_eq!(kreis!("10041"), "10041".parse::<KreisSchluessel>().unwrap());
+        assert_eq!(region!("0811"), "0811".parse::<RegionSchluessel>().unwrap());
+        assert_eq!(
+            gemeindeverband!("100410100"),
+            "100410100".parse::<GemeindeverbandSchluessel>().unwrap()
+        );
+        assert_eq!(
+            gemeinde!("100410100100"),
+            "100410100100".parse::<GemeindeSchluessel>().unwrap()
+        );
+        assert_eq!(
+            ags!("10041100"),
+            "10041100".parse::<RegionalSchluessel>().unwrap()
+        );
+        assert_eq!(land!("01").land, 1);
+        assert_eq!(regierungsbezirk!("091").regierungsbezirk, 1);
+    }
+
+    #[test]
+    fn keys_are_displayed_with_leading_zeros() {
+        assert_eq!(kreis!("01001").to_string(), "01001");
+        assert_eq!(region!("0811").to_string(), "0811");
+        assert_eq!(gemeindeverband!("010010000").to_string(), "010010000");
+    }
+}
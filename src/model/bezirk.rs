@@ -0,0 +1,81 @@
+//! Functional districts (Bezirke), that don't follow the administrative
+//! hierarchy: Finanzamtbezirke, Arbeitsagenturbezirke, Gerichtsbezirke and
+//! Bundestagswahlkreise. Each Gemeinde belongs to one district of each kind,
+//! except for large cities that are split over several Wahlkreise.
+
+use std::fmt::{self, Display, Formatter};
+
+use super::gericht::{
+    AmtsgerichtSchluessel,
+    Gericht,
+    LandgerichtSchluessel,
+    OberlandesgerichtSchluessel,
+};
+
+/// A functional district.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Bezirk {
+    /// District of a Finanzamt (tax office)
+    Finanzamt(u16),
+
+    /// District of an Agentur für Arbeit (employment agency)
+    Arbeitsagentur(u32),
+
+    /// District of a court. The district of an Oberlandesgericht or
+    /// Landgericht includes the districts of all its Amtsgerichte.
+    Gericht(Gericht),
+
+    /// Bundestagswahlkreis, by number
+    Bundestagswahlkreis(u16),
+}
+
+impl Bezirk {
+    /// Returns the German label of the kind of district.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Finanzamt(_) => "Finanzamtbezirk",
+            Self::Arbeitsagentur(_) => "Arbeitsagenturbezirk",
+            Self::Gericht(Gericht::Oberlandesgericht(_)) => "Oberlandesgerichtsbezirk",
+            Self::Gericht(Gericht::Landgericht(_)) => "Landgerichtsbezirk",
+            Self::Gericht(Gericht::Amtsgericht(_)) => "Amtsgerichtsbezirk",
+            Self::Bundestagswahlkreis(_) => "Bundestagswahlkreis",
+        }
+    }
+}
+
+impl From<Gericht> for Bezirk {
+    fn from(gericht: Gericht) -> Self {
+        Self::Gericht(gericht)
+    }
+}
+
+impl From<OberlandesgerichtSchluessel> for Bezirk {
+    fn from(oberlandesgericht: OberlandesgerichtSchluessel) -> Self {
+        Self::Gericht(oberlandesgericht.into())
+    }
+}
+
+impl From<LandgerichtSchluessel> for Bezirk {
+    fn from(landgericht: LandgerichtSchluessel) -> Self {
+        Self::Gericht(landgericht.into())
+    }
+}
+
+impl From<AmtsgerichtSchluessel> for Bezirk {
+    fn from(amtsgericht: AmtsgerichtSchluessel) -> Self {
+        Self::Gericht(amtsgericht.into())
+    }
+}
+
+impl Display for Bezirk {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Finanzamt(finanzamt) => write!(f, "{} {:04}", self.label(), finanzamt),
+            Self::Arbeitsagentur(arbeitsagentur) => {
+                write!(f, "{} {:05}", self.label(), arbeitsagentur)
+            }
+            Self::Gericht(gericht) => write!(f, "{} {}", self.label(), gericht),
+            Self::Bundestagswahlkreis(nummer) => write!(f, "{} {}", self.label(), nummer),
+        }
+    }
+}
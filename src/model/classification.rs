@@ -0,0 +1,279 @@
+//! Classification of Gemeinden by population size (Gemeindegrößenklassen) and
+//! settlement type (e.g. Großstadt).
+
+use std::fmt::{self, Display, Formatter};
+
+use super::gemeinde::GemeindeDaten;
+
+/// Lower bounds of the Gemeindegrößenklassen used by Destatis.
+const DESTATIS_BOUNDS: [u64; 10] = [
+    0, 1_000, 2_000, 5_000, 10_000, 20_000, 50_000, 100_000, 200_000, 500_000,
+];
+
+/// Gemeindegrößenklasse as used by Destatis.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum SizeClass {
+    Under1000,
+    From1000To2000,
+    From2000To5000,
+    From5000To10000,
+    From10000To20000,
+    From20000To50000,
+    From50000To100000,
+    From100000To200000,
+    From200000To500000,
+    From500000,
+}
+
+impl SizeClass {
+    /// All size classes in ascending order.
+    pub const ALL: [SizeClass; 10] = [
+        Self::Under1000,
+        Self::From1000To2000,
+        Self::From2000To5000,
+        Self::From5000To10000,
+        Self::From10000To20000,
+        Self::From20000To50000,
+        Self::From50000To100000,
+        Self::From100000To200000,
+        Self::From200000To500000,
+        Self::From500000,
+    ];
+
+    /// Returns the size class for a population.
+    pub fn from_population(population: u64) -> Self {
+        let index = DESTATIS_BOUNDS
+            .iter()
+            .rposition(|lower| population >= *lower)
+            .unwrap_or_default();
+        Self::ALL[index]
+    }
+
+    /// Returns the range of population of this size class.
+    pub fn range(&self) -> SizeClassRange {
+        let index = *self as usize;
+        SizeClassRange {
+            lower: DESTATIS_BOUNDS[index],
+            upper: DESTATIS_BOUNDS.get(index + 1).copied(),
+        }
+    }
+
+    /// Returns the German label, e.g. `1 000 bis unter 2 000`.
+    pub fn label(&self) -> String {
+        self.range().to_string()
+    }
+}
+
+impl Display for SizeClass {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.range())
+    }
+}
+
+/// A range of population. The lower bound is inclusive, the upper bound is
+/// exclusive.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SizeClassRange {
+    pub lower: u64,
+    pub upper: Option<u64>,
+}
+
+impl SizeClassRange {
+    /// Whether `population` lies in this range.
+    pub fn contains(&self, population: u64) -> bool {
+        population >= self.lower && self.upper.map_or(true, |upper| population < upper)
+    }
+}
+
+impl Display for SizeClassRange {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match (self.lower, self.upper) {
+            (0, Some(upper)) => write!(f, "unter {}", Thousands(upper)),
+            (lower, Some(upper)) => {
+                write!(f, "{} bis unter {}", Thousands(lower), Thousands(upper))
+            }
+            (lower, None) => write!(f, "{} und mehr", Thousands(lower)),
+        }
+    }
+}
+
+/// Formats numbers with a space as thousands separator, as in Destatis
+/// publications.
+struct Thousands(u64);
+
+impl Display for Thousands {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let digits = self.0.to_string();
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+/// Custom size classes, defined by their lower bounds.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SizeClasses {
+    bounds: Vec<u64>,
+}
+
+impl SizeClasses {
+    /// Creates size classes from their lower bounds. The first class always
+    /// starts at 0.
+    pub fn new(mut bounds: Vec<u64>) -> Self {
+        bounds.push(0);
+        bounds.sort_unstable();
+        bounds.dedup();
+        Self { bounds }
+    }
+
+    /// Returns the size class for a population.
+    pub fn classify(&self, population: u64) -> SizeClassRange {
+        let index = self
+            .bounds
+            .iter()
+            .rposition(|lower| population >= *lower)
+            .unwrap_or_default();
+        self.range(index)
+    }
+
+    /// Returns all size classes in ascending order.
+    pub fn ranges(&self) -> impl Iterator<Item = SizeClassRange> + '_ {
+        (0..self.bounds.len()).map(move |index| self.range(index))
+    }
+
+    fn range(&self, index: usize) -> SizeClassRange {
+        SizeClassRange {
+            lower: self.bounds[index],
+            upper: self.bounds.get(index + 1).copied(),
+        }
+    }
+}
+
+/// The Destatis Gemeindegrößenklassen.
+impl Default for SizeClasses {
+    fn default() -> Self {
+        Self::new(DESTATIS_BOUNDS.to_vec())
+    }
+}
+
+/// Type of settlement.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum SettlementType {
+    /// Less than 5 000 inhabitants and no city status
+    Landgemeinde,
+
+    /// 5 000 to less than 20 000 inhabitants, or city status
+    Kleinstadt,
+
+    /// 20 000 to less than 100 000 inhabitants
+    Mittelstadt,
+
+    /// 100 000 inhabitants and more
+    Grossstadt,
+}
+
+impl SettlementType {
+    /// Returns the settlement type of a Gemeinde. Returns `None` for
+    /// gemeindefreie Gebiete.
+    pub fn from_gemeinde(gemeinde: &GemeindeDaten) -> Option<Self> {
+        if gemeinde.is_gemeindefrei() {
+            return None;
+        }
+
+        let settlement_type = match gemeinde.population_total {
+            n if n >= 100_000 => Self::Grossstadt,
+            n if n >= 20_000 => Self::Mittelstadt,
+            n if n >= 5_000 => Self::Kleinstadt,
+            _ if gemeinde.textkennzeichen.is_city() => Self::Kleinstadt,
+            _ => Self::Landgemeinde,
+        };
+        Some(settlement_type)
+    }
+
+    /// Returns the German label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Landgemeinde => "Landgemeinde",
+            Self::Kleinstadt => "Kleinstadt",
+            Self::Mittelstadt => "Mittelstadt",
+            Self::Grossstadt => "Großstadt",
+        }
+    }
+}
+
+impl Display for SettlementType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+impl GemeindeDaten {
+    /// Returns the Gemeindegrößenklasse.
+    pub fn size_class(&self) -> SizeClass {
+        SizeClass::from_population(self.population_total)
+    }
+
+    /// Returns the settlement type. Returns `None` for gemeindefreie Gebiete.
+    pub fn settlement_type(&self) -> Option<SettlementType> {
+        SettlementType::from_gemeinde(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_classifies_by_population() {
+        assert_eq!(SizeClass::from_population(0), SizeClass::Under1000);
+        assert_eq!(SizeClass::from_population(999), SizeClass::Under1000);
+        assert_eq!(SizeClass::from_population(1000), SizeClass::From1000To2000);
+        assert_eq!(
+            SizeClass::from_population(180374),
+            SizeClass::From100000To200000
+        );
+        assert_eq!(SizeClass::from_population(3_600_000), SizeClass::From500000);
+    }
+
+    #[test]
+    fn it_formats_labels() {
+        assert_eq!(SizeClass::Under1000.label(), "unter 1 000");
+        assert_eq!(
+            SizeClass::From20000To50000.label(),
+            "20 000 bis unter 50 000"
+        );
+        assert_eq!(SizeClass::From500000.label(), "500 000 und mehr");
+    }
+
+    #[test]
+    fn it_uses_custom_size_classes() {
+        let classes = SizeClasses::new(vec![10_000, 50_000]);
+        assert_eq!(classes.ranges().count(), 3);
+        assert_eq!(
+            classes.classify(12_000),
+            SizeClassRange {
+                lower: 10_000,
+                upper: Some(50_000)
+            }
+        );
+        assert_eq!(
+            classes.classify(50_000),
+            SizeClassRange {
+                lower: 50_000,
+                upper: None
+            }
+        );
+    }
+
+    #[test]
+    fn default_size_classes_match_destatis() {
+        let classes = SizeClasses::default();
+        for class in &SizeClass::ALL {
+            assert_eq!(classes.classify(class.range().lower), class.range());
+        }
+    }
+}
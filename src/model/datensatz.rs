@@ -1,12 +1,15 @@
+use std::fmt::{self, Display, Formatter};
+
 use chrono::NaiveDate;
 
 use super::{
-    gemeinde::GemeindeDaten,
-    gemeindeverband::GemeindeverbandDaten,
-    kreis::KreisDaten,
-    land::LandDaten,
-    regierungsbezirk::RegierungsbezirkDaten,
-    region::RegionDaten,
+    gemeinde::{GemeindeDaten, GemeindeSchluessel},
+    gemeindeverband::{GemeindeverbandDaten, GemeindeverbandSchluessel},
+    kreis::{KreisDaten, KreisSchluessel},
+    land::{LandDaten, LandSchluessel},
+    record::Record,
+    regierungsbezirk::{RegierungsbezirkDaten, RegierungsbezirkSchluessel},
+    region::{RegionDaten, RegionSchluessel},
 };
 
 /// A GV100AD Daten (Datensatz).
@@ -23,25 +26,212 @@ pub enum Datensatz {
 impl Datensatz {
     /// Returns the Gebietsstand (i.e. timestamp) of the Daten.
     pub fn gebietsstand(&self) -> &NaiveDate {
-        match self {
+        Record::gebietsstand(self)
+    }
+
+    /// Returns the name of the unit.
+    pub fn name(&self) -> &str {
+        Record::name(self).as_str()
+    }
+}
+
+/// A borrowed GV100AD Daten (Datensatz), e.g. as returned by
+/// [`Database::datensatz`](crate::Database::datensatz).
+#[derive(Copy, Clone, Debug)]
+pub enum DatensatzRef<'a> {
+    Land(&'a LandDaten),
+    Regierungsbezirk(&'a RegierungsbezirkDaten),
+    Region(&'a RegionDaten),
+    Kreis(&'a KreisDaten),
+    Gemeindeverband(&'a GemeindeverbandDaten),
+    Gemeinde(&'a GemeindeDaten),
+}
+
+impl<'a> DatensatzRef<'a> {
+    /// Returns the key of the unit.
+    pub fn schluessel(&self) -> Schluessel {
+        Record::schluessel(self)
+    }
+
+    /// Returns the Gebietsstand (i.e. timestamp) of the Daten. Unlike
+    /// [`Record::gebietsstand`], the reference lives as long as the database.
+    pub fn gebietsstand(&self) -> &'a NaiveDate {
+        match *self {
             Self::Land(land) => &land.gebietsstand,
             Self::Regierungsbezirk(regierungsbezirk) => &regierungsbezirk.gebietsstand,
-            Self::Region(_region) => todo!(),
+            Self::Region(region) => &region.gebietsstand,
             Self::Kreis(kreis) => &kreis.gebietsstand,
             Self::Gemeindeverband(gemeindeverband) => &gemeindeverband.gebietsstand,
             Self::Gemeinde(gemeinde) => &gemeinde.gebietsstand,
         }
     }
 
-    /// Returns the name of the unit.
-    pub fn name(&self) -> &str {
+    /// Returns the name of the unit, like [`Datensatz::name`]. Unlike
+    /// [`Record::name`], the reference lives as long as the database.
+    pub fn name(&self) -> &'a str {
+        match *self {
+            Self::Land(land) => land.name.as_str(),
+            Self::Regierungsbezirk(regierungsbezirk) => regierungsbezirk.name.as_str(),
+            Self::Region(region) => region.name.as_str(),
+            Self::Kreis(kreis) => kreis.name.as_str(),
+            Self::Gemeindeverband(gemeindeverband) => gemeindeverband.name.as_str(),
+            Self::Gemeinde(gemeinde) => gemeinde.name.as_str(),
+        }
+    }
+
+    /// Clones the record into an owned [`Datensatz`].
+    pub fn to_datensatz(&self) -> Datensatz {
+        match self {
+            Self::Land(land) => Datensatz::Land((*land).clone()),
+            Self::Regierungsbezirk(regierungsbezirk) => {
+                Datensatz::Regierungsbezirk((*regierungsbezirk).clone())
+            }
+            Self::Region(region) => Datensatz::Region((*region).clone()),
+            Self::Kreis(kreis) => Datensatz::Kreis((*kreis).clone()),
+            Self::Gemeindeverband(gemeindeverband) => {
+                Datensatz::Gemeindeverband((*gemeindeverband).clone())
+            }
+            Self::Gemeinde(gemeinde) => Datensatz::Gemeinde((*gemeinde).clone()),
+        }
+    }
+}
+
+impl<'a> From<&'a Datensatz> for DatensatzRef<'a> {
+    fn from(datensatz: &'a Datensatz) -> Self {
+        match datensatz {
+            Datensatz::Land(land) => Self::Land(land),
+            Datensatz::Regierungsbezirk(regierungsbezirk) => {
+                Self::Regierungsbezirk(regierungsbezirk)
+            }
+            Datensatz::Region(region) => Self::Region(region),
+            Datensatz::Kreis(kreis) => Self::Kreis(kreis),
+            Datensatz::Gemeindeverband(gemeindeverband) => Self::Gemeindeverband(gemeindeverband),
+            Datensatz::Gemeinde(gemeinde) => Self::Gemeinde(gemeinde),
+        }
+    }
+}
+
+/// A key of any level.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Schluessel {
+    Land(LandSchluessel),
+    Regierungsbezirk(RegierungsbezirkSchluessel),
+    Region(RegionSchluessel),
+    Kreis(KreisSchluessel),
+    Gemeindeverband(GemeindeverbandSchluessel),
+    Gemeinde(GemeindeSchluessel),
+}
+
+impl Schluessel {
+    /// Returns the key of the Land of the unit.
+    pub fn land(&self) -> LandSchluessel {
+        match self {
+            Self::Land(schluessel) => *schluessel,
+            Self::Regierungsbezirk(schluessel) => (*schluessel).into(),
+            Self::Region(schluessel) => schluessel.regierungsbezirk.into(),
+            Self::Kreis(schluessel) => (*schluessel).into(),
+            Self::Gemeindeverband(schluessel) => (*schluessel).into(),
+            Self::Gemeinde(schluessel) => (*schluessel).into(),
+        }
+    }
+
+    /// Returns the key of the Regierungsbezirk of the unit, unless it's a
+    /// Land.
+    pub fn regierungsbezirk(&self) -> Option<RegierungsbezirkSchluessel> {
+        match self {
+            Self::Land(_) => None,
+            Self::Regierungsbezirk(schluessel) => Some(*schluessel),
+            Self::Region(schluessel) => Some(schluessel.regierungsbezirk),
+            Self::Kreis(schluessel) => Some((*schluessel).into()),
+            Self::Gemeindeverband(schluessel) => Some((*schluessel).into()),
+            Self::Gemeinde(schluessel) => Some((*schluessel).into()),
+        }
+    }
+
+    /// Returns the key of the Kreis of the unit, if it's a Kreis or below.
+    pub fn kreis(&self) -> Option<KreisSchluessel> {
+        match self {
+            Self::Kreis(schluessel) => Some(*schluessel),
+            Self::Gemeindeverband(schluessel) => Some((*schluessel).into()),
+            Self::Gemeinde(schluessel) => Some((*schluessel).into()),
+            _ => None,
+        }
+    }
+
+    /// Returns the key of the Gemeindeverband of the unit, if it's a
+    /// Gemeindeverband or Gemeinde.
+    pub fn gemeindeverband(&self) -> Option<GemeindeverbandSchluessel> {
+        match self {
+            Self::Gemeindeverband(schluessel) => Some(*schluessel),
+            Self::Gemeinde(schluessel) => Some((*schluessel).into()),
+            _ => None,
+        }
+    }
+
+    /// Whether the unit with key `other` is this unit, or lies within it.
+    pub fn contains<K: Into<Schluessel>>(&self, other: K) -> bool {
+        let other = other.into();
+        match self {
+            Self::Land(schluessel) => other.land() == *schluessel,
+            Self::Regierungsbezirk(schluessel) => other.regierungsbezirk() == Some(*schluessel),
+            Self::Region(schluessel) => {
+                other == *self
+                    || other
+                        .kreis()
+                        .is_some_and(|kreis| schluessel.contains(kreis))
+            }
+            Self::Kreis(schluessel) => other.kreis() == Some(*schluessel),
+            Self::Gemeindeverband(schluessel) => other.gemeindeverband() == Some(*schluessel),
+            Self::Gemeinde(_) => other == *self,
+        }
+    }
+}
+
+impl From<LandSchluessel> for Schluessel {
+    fn from(schluessel: LandSchluessel) -> Self {
+        Self::Land(schluessel)
+    }
+}
+
+impl From<RegierungsbezirkSchluessel> for Schluessel {
+    fn from(schluessel: RegierungsbezirkSchluessel) -> Self {
+        Self::Regierungsbezirk(schluessel)
+    }
+}
+
+impl From<RegionSchluessel> for Schluessel {
+    fn from(schluessel: RegionSchluessel) -> Self {
+        Self::Region(schluessel)
+    }
+}
+
+impl From<KreisSchluessel> for Schluessel {
+    fn from(schluessel: KreisSchluessel) -> Self {
+        Self::Kreis(schluessel)
+    }
+}
+
+impl From<GemeindeverbandSchluessel> for Schluessel {
+    fn from(schluessel: GemeindeverbandSchluessel) -> Self {
+        Self::Gemeindeverband(schluessel)
+    }
+}
+
+impl From<GemeindeSchluessel> for Schluessel {
+    fn from(schluessel: GemeindeSchluessel) -> Self {
+        Self::Gemeinde(schluessel)
+    }
+}
+
+impl Display for Schluessel {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
-            Self::Land(land) => &land.name,
-            Self::Regierungsbezirk(regierungsbezirk) => &regierungsbezirk.name,
-            Self::Region(_region) => todo!(),
-            Self::Kreis(kreis) => &kreis.name,
-            Self::Gemeindeverband(gemeindeverband) => &gemeindeverband.name,
-            Self::Gemeinde(gemeinde) => &gemeinde.name,
+            Self::Land(schluessel) => write!(f, "{}", schluessel),
+            Self::Regierungsbezirk(schluessel) => write!(f, "{}", schluessel),
+            Self::Region(schluessel) => write!(f, "{}", schluessel),
+            Self::Kreis(schluessel) => write!(f, "{}", schluessel),
+            Self::Gemeindeverband(schluessel) => write!(f, "{}", schluessel),
+            Self::Gemeinde(schluessel) => write!(f, "{}", schluessel),
         }
     }
 }
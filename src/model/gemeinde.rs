@@ -6,15 +6,23 @@ use std::{
 
 use chrono::NaiveDate;
 
-use crate::error::{Error, ParseKeyError};
+use crate::{
+    error::{Error, ParseKeyError},
+    macros::{check_literal_length, literal_digits},
+};
 
 use super::{
     gemeindeverband::GemeindeverbandSchluessel,
     kreis::KreisSchluessel,
     land::LandSchluessel,
+    name::Name,
+    plz::Plz,
     regierungsbezirk::RegierungsbezirkSchluessel,
+    statistics::Area,
 };
 
+pub use super::{gericht::Gerichtbarkeit, wahlkreis::Bundestagswahlkreise};
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct RegionalSchluessel {
     pub kreis: KreisSchluessel,
@@ -22,10 +30,25 @@ pub struct RegionalSchluessel {
 }
 
 impl RegionalSchluessel {
-    pub fn new(kreis: KreisSchluessel, gemeinde: u16) -> Self {
+    pub const fn new(kreis: KreisSchluessel, gemeinde: u16) -> Self {
         Self { kreis, gemeinde }
     }
 
+    /// Creates a key from a literal (e.g. `"10041100"`). This is used by the
+    /// [`ags!`](crate::ags) macro.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not a valid key. In const context this is a compile
+    /// error.
+    pub const fn from_literal(s: &str) -> Self {
+        check_literal_length(s, 8);
+        Self::new(
+            KreisSchluessel::from_literal_digits(s),
+            literal_digits(s, 5, 8),
+        )
+    }
+
     pub fn to_gemeinde_schluessel(self, gemeindeverband: u16) -> GemeindeSchluessel {
         GemeindeSchluessel::from_regional_schluessel(self, gemeindeverband)
     }
@@ -48,7 +71,7 @@ impl FromStr for RegionalSchluessel {
 
 impl Display for RegionalSchluessel {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}{:3}", self.kreis, self.gemeinde)
+        write!(f, "{}{:03}", self.kreis, self.gemeinde)
     }
 }
 
@@ -77,13 +100,28 @@ pub struct GemeindeSchluessel {
 }
 
 impl GemeindeSchluessel {
-    pub fn new(gemeindeverband: GemeindeverbandSchluessel, gemeinde: u16) -> Self {
+    pub const fn new(gemeindeverband: GemeindeverbandSchluessel, gemeinde: u16) -> Self {
         Self {
             gemeindeverband,
             gemeinde,
         }
     }
 
+    /// Creates a key from a literal (e.g. `"100410100100"`). This is used by
+    /// the [`gemeinde!`](crate::gemeinde) macro.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not a valid key. In const context this is a compile
+    /// error.
+    pub const fn from_literal(s: &str) -> Self {
+        check_literal_length(s, 12);
+        Self::new(
+            GemeindeverbandSchluessel::from_literal_digits(s),
+            literal_digits(s, 9, 12),
+        )
+    }
+
     pub fn from_regional_schluessel(
         regional_schluessel: RegionalSchluessel,
         gemeindeverband: u16,
@@ -115,7 +153,7 @@ impl FromStr for GemeindeSchluessel {
 
 impl Display for GemeindeSchluessel {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}{:3}", self.gemeindeverband, self.gemeinde)
+        write!(f, "{}{:03}", self.gemeindeverband, self.gemeinde)
     }
 }
 
@@ -162,13 +200,13 @@ pub struct GemeindeDaten {
     pub schluessel: GemeindeSchluessel,
 
     /// Name of Gemeinde
-    pub name: String,
+    pub name: Name,
 
     /// Specifies type of Gemeinde
     pub textkennzeichen: GemeindeTextkennzeichen,
 
-    /// Area in hectare (10000 square-meter)
-    pub area: u64,
+    /// Area
+    pub area: Area,
 
     /// Total population
     pub population_total: u64,
@@ -176,12 +214,19 @@ pub struct GemeindeDaten {
     /// Male population
     pub population_male: u64,
 
-    /// Postleitzahl (PLZ, Postcode)
-    pub plz: String,
+    /// Postleitzahl (PLZ, Postcode). If the Gemeinde has more than one PLZ,
+    /// this is the PLZ of the administration.
+    pub plz: Option<Plz>,
 
-    /// Whether the PLZ is unambiguous or not
+    /// Whether the PLZ is unambiguous or not, i.e. if the Gemeinde has only
+    /// one PLZ.
     pub plz_unambiguous: bool,
 
+    /// All PLZ of the Gemeinde. The GV100AD file only contains one PLZ per
+    /// Gemeinde, so initially this only contains `plz`. Use
+    /// [`Database::import_plz`](crate::Database::import_plz) to import all PLZ.
+    pub all_plz: Vec<Plz>,
+
     pub finanzamtbezirk: Option<u16>,
 
     pub gerichtbarkeit: Option<Gerichtbarkeit>,
@@ -197,50 +242,7 @@ impl GemeindeDaten {
     }
 }
 
-/// Information regarding juristical districts
-#[derive(Clone, Debug)]
-pub struct Gerichtbarkeit {
-    pub oberlandesgericht: String,
-    pub landgericht: String,
-    pub amtsgericht: String,
-}
-
-impl FromStr for Gerichtbarkeit {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Gerichtbarkeit {
-            oberlandesgericht: s[0..1].to_owned(),
-            landgericht: s[1..2].to_owned(),
-            amtsgericht: s[2..4].to_owned(),
-        })
-    }
-}
-
-/// Associated election districts. If `Range`, it can include gaps.
-#[derive(Clone, Debug)]
-pub enum Bundestagswahlkreise {
-    Single(u16),
-    Range(u16, u16),
-}
-
-impl FromStr for Bundestagswahlkreise {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let von = s[..3].parse()?;
-        tracing::trace!(von = ?von);
-
-        let bis = &s[3..];
-        tracing::trace!(bis = ?bis);
-        if bis.chars().all(|c| c == ' ') {
-            Ok(Bundestagswahlkreise::Single(von))
-        } else {
-            Ok(Bundestagswahlkreise::Range(von, bis.parse()?))
-        }
-    }
-}
-
+/// Type of Gemeinde
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum GemeindeTextkennzeichen {
     Markt,
@@ -270,3 +272,98 @@ impl TryFrom<u8> for GemeindeTextkennzeichen {
         }
     }
 }
+
+impl From<GemeindeTextkennzeichen> for u8 {
+    fn from(textkennzeichen: GemeindeTextkennzeichen) -> Self {
+        textkennzeichen.code()
+    }
+}
+
+impl GemeindeTextkennzeichen {
+    /// Returns the numeric code as used in the GV100AD file.
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::Markt => 60,
+            Self::KreisfreieStadt => 61,
+            Self::Stadtkreis => 62,
+            Self::Stadt => 63,
+            Self::KreisangehoerigeGemeinde => 64,
+            Self::GemeindefreiesGebietBewohnt => 65,
+            Self::GemeindefreiesGebietUnbewohnt => 66,
+            Self::GrosseKreisstadt => 67,
+        }
+    }
+
+    /// Returns the official German label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Markt => "Markt",
+            Self::KreisfreieStadt => "Kreisfreie Stadt",
+            Self::Stadtkreis => "Stadtkreis",
+            Self::Stadt => "Stadt",
+            Self::KreisangehoerigeGemeinde => "Kreisangehörige Gemeinde",
+            Self::GemeindefreiesGebietBewohnt => "Gemeindefreies Gebiet, bewohnt",
+            Self::GemeindefreiesGebietUnbewohnt => "Gemeindefreies Gebiet, unbewohnt",
+            Self::GrosseKreisstadt => "Große Kreisstadt",
+        }
+    }
+
+    /// Returns an English label.
+    pub fn label_en(&self) -> &'static str {
+        match self {
+            Self::Markt => "Market town",
+            Self::KreisfreieStadt => "District-free city",
+            Self::Stadtkreis => "Urban district",
+            Self::Stadt => "Town",
+            Self::KreisangehoerigeGemeinde => "Municipality belonging to a district",
+            Self::GemeindefreiesGebietBewohnt => "Unincorporated area, inhabited",
+            Self::GemeindefreiesGebietUnbewohnt => "Unincorporated area, uninhabited",
+            Self::GrosseKreisstadt => "Major district town",
+        }
+    }
+
+    /// Whether the Gemeinde is a city (i.e. a Kreisfreie Stadt, Stadtkreis,
+    /// Stadt or Große Kreisstadt).
+    pub fn is_city(&self) -> bool {
+        matches!(
+            self,
+            Self::KreisfreieStadt | Self::Stadtkreis | Self::Stadt | Self::GrosseKreisstadt
+        )
+    }
+
+    /// Whether the Gemeinde is a city that doesn't belong to a Kreis.
+    pub fn is_kreisfrei(&self) -> bool {
+        matches!(self, Self::KreisfreieStadt | Self::Stadtkreis)
+    }
+
+    /// Whether this is an unincorporated area (gemeindefreies Gebiet).
+    pub fn is_unincorporated(&self) -> bool {
+        matches!(
+            self,
+            Self::GemeindefreiesGebietBewohnt | Self::GemeindefreiesGebietUnbewohnt
+        )
+    }
+}
+
+impl Display for GemeindeTextkennzeichen {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_displayed_with_leading_zeros() {
+        let gemeinde = "010010000005".parse::<GemeindeSchluessel>().unwrap();
+        assert_eq!(gemeinde.gemeinde, 5);
+        assert_eq!(gemeinde.to_string(), "010010000005");
+
+        let ags = "01001005".parse::<RegionalSchluessel>().unwrap();
+        assert_eq!(ags.gemeinde, 5);
+        assert_eq!(ags.to_string(), "01001005");
+        assert_eq!(RegionalSchluessel::from(gemeinde), ags);
+    }
+}
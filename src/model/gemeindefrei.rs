@@ -0,0 +1,209 @@
+//! Unincorporated areas (gemeindefreie Gebiete). These are stored as Gemeinden
+//! in the GV100AD file, but are not counted as Gemeinden by Destatis. Their
+//! area and population are included in the totals of the Kreis and Land
+//! though.
+
+use std::fmt::{self, Display, Formatter};
+
+use super::gemeinde::{GemeindeDaten, GemeindeTextkennzeichen, RegionalSchluessel};
+
+/// Kind of land of gemeindefreie Gebiete that can't be told from their name,
+/// by AGS. These are mostly military training areas named after the landscape
+/// (e.g. `Osterheide`).
+const GEBIETSARTEN: &[(RegionalSchluessel, Gebietsart)] = &[
+    // Lohheide (Truppenübungsplatz Bergen)
+    (
+        RegionalSchluessel::from_literal("03351501"),
+        Gebietsart::Truppenuebungsplatz,
+    ),
+    // Osterheide (Truppenübungsplatz Bergen)
+    (
+        RegionalSchluessel::from_literal("03358501"),
+        Gebietsart::Truppenuebungsplatz,
+    ),
+    // Gutsbezirk Münsingen (former Truppenübungsplatz Münsingen)
+    (
+        RegionalSchluessel::from_literal("08415100"),
+        Gebietsart::Truppenuebungsplatz,
+    ),
+];
+
+/// Which records to include when iterating over, counting or aggregating
+/// Gemeinden. The default includes gemeindefreie Gebiete, as Destatis does for
+/// area and population totals.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum GemeindefreiFilter {
+    /// Gemeinden and gemeindefreie Gebiete
+    #[default]
+    Include,
+
+    /// Only Gemeinden
+    Exclude,
+
+    /// Only gemeindefreie Gebiete
+    Only,
+}
+
+impl GemeindefreiFilter {
+    /// Whether `gemeinde` passes the filter.
+    pub fn matches(&self, gemeinde: &GemeindeDaten) -> bool {
+        match self {
+            Self::Include => true,
+            Self::Exclude => !gemeinde.is_gemeindefrei(),
+            Self::Only => gemeinde.is_gemeindefrei(),
+        }
+    }
+}
+
+/// Kind of land of a gemeindefreies Gebiet.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Gebietsart {
+    /// Forest (e.g. `Ebersberger Forst`, `Sachsenwald`)
+    Forst,
+
+    /// Lake (e.g. `Starnberger See`)
+    Gewaesser,
+
+    /// Military training area (e.g. `Truppenübungsplatz Grafenwöhr`)
+    Truppenuebungsplatz,
+
+    /// Other, or unknown
+    Sonstiges,
+}
+
+impl Gebietsart {
+    /// Returns the kind of land of the gemeindefreies Gebiet with AGS `ags`.
+    /// The GV100AD file doesn't contain this information, so it's taken from a
+    /// table of known areas, or guessed from the name otherwise (see
+    /// [`Gebietsart::from_name`]).
+    pub fn of(ags: RegionalSchluessel, name: &str) -> Self {
+        GEBIETSARTEN
+            .iter()
+            .find(|(schluessel, _)| *schluessel == ags)
+            .map(|(_, art)| *art)
+            .unwrap_or_else(|| Self::from_name(name))
+    }
+
+    /// Guesses the kind of land from the name of the gemeindefreies Gebiet.
+    /// This only works for names that contain the kind of land (e.g.
+    /// `Truppenübungsplatz Grafenwöhr` or `Ebersberger Forst`).
+    pub fn from_name(name: &str) -> Self {
+        let name = name.to_lowercase();
+        let words = || name.split(|c: char| !c.is_alphanumeric());
+
+        if name.contains("übungsplatz") {
+            Self::Truppenuebungsplatz
+        } else if words().any(|word| word == "see" || word.ends_with("see")) {
+            Self::Gewaesser
+        } else if name.contains("forst") || name.contains("wald") {
+            Self::Forst
+        } else {
+            Self::Sonstiges
+        }
+    }
+
+    /// Returns the German label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Forst => "Forst",
+            Self::Gewaesser => "Gewässer",
+            Self::Truppenuebungsplatz => "Truppenübungsplatz",
+            Self::Sonstiges => "Sonstiges",
+        }
+    }
+
+    /// Whether the area is used by the military.
+    pub fn is_military(&self) -> bool {
+        matches!(self, Self::Truppenuebungsplatz)
+    }
+}
+
+impl Display for Gebietsart {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// Metadata of a gemeindefreies Gebiet.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GemeindefreiesGebiet {
+    /// Whether the area is inhabited
+    pub bewohnt: bool,
+
+    /// Kind of land
+    pub art: Gebietsart,
+}
+
+impl GemeindeDaten {
+    /// Whether this is a gemeindefreies Gebiet (unincorporated area), i.e. not
+    /// a Gemeinde.
+    pub fn is_gemeindefrei(&self) -> bool {
+        self.textkennzeichen.is_unincorporated()
+    }
+
+    /// Returns metadata if this is a gemeindefreies Gebiet.
+    pub fn gemeindefreies_gebiet(&self) -> Option<GemeindefreiesGebiet> {
+        if !self.is_gemeindefrei() {
+            return None;
+        }
+
+        Some(GemeindefreiesGebiet {
+            bewohnt: self.textkennzeichen == GemeindeTextkennzeichen::GemeindefreiesGebietBewohnt,
+            art: Gebietsart::of(self.schluessel.into(), self.name.as_str()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_guesses_gebietsart() {
+        assert_eq!(
+            Gebietsart::from_name("Truppenübungsplatz Grafenwöhr"),
+            Gebietsart::Truppenuebungsplatz
+        );
+        assert_eq!(
+            Gebietsart::from_name("Gutsbezirk Reinhardswald"),
+            Gebietsart::Forst
+        );
+        assert_eq!(
+            Gebietsart::from_name("Starnberger See"),
+            Gebietsart::Gewaesser
+        );
+        assert_eq!(Gebietsart::from_name("Chiemsee"), Gebietsart::Gewaesser);
+        assert_eq!(
+            Gebietsart::from_name("Ebersberger Forst"),
+            Gebietsart::Forst
+        );
+        assert_eq!(Gebietsart::from_name("Sachsenwald"), Gebietsart::Forst);
+        assert_eq!(Gebietsart::from_name("Seeberg"), Gebietsart::Sonstiges);
+    }
+
+    #[test]
+    fn it_looks_up_gebietsart_by_ags() {
+        let osterheide = "03358501".parse().unwrap();
+        assert_eq!(
+            Gebietsart::of(osterheide, "Osterheide, gemfr. Bezirk"),
+            Gebietsart::Truppenuebungsplatz
+        );
+        assert!(Gebietsart::of(osterheide, "Osterheide, gemfr. Bezirk").is_military());
+        assert_eq!(
+            Gebietsart::from_name("Osterheide, gemfr. Bezirk"),
+            Gebietsart::Sonstiges
+        );
+
+        let muensingen = "08415100".parse().unwrap();
+        assert_eq!(
+            Gebietsart::of(muensingen, "Gutsbezirk Münsingen"),
+            Gebietsart::Truppenuebungsplatz
+        );
+
+        let ebersberger_forst = "09175451".parse().unwrap();
+        assert_eq!(
+            Gebietsart::of(ebersberger_forst, "Ebersberger Forst"),
+            Gebietsart::Forst
+        );
+    }
+}
@@ -9,10 +9,14 @@ use chrono::NaiveDate;
 use super::{
     kreis::KreisSchluessel,
     land::LandSchluessel,
+    name::Name,
     regierungsbezirk::RegierungsbezirkSchluessel,
 };
 
-use crate::error::{Error, ParseKeyError};
+use crate::{
+    error::{Error, ParseKeyError},
+    macros::{check_literal_length, literal_digits},
+};
 
 #[derive(Clone, Debug)]
 pub struct GemeindeverbandDaten {
@@ -23,7 +27,7 @@ pub struct GemeindeverbandDaten {
     pub schluessel: GemeindeverbandSchluessel,
 
     /// Name of Gemeindeverband
-    pub name: String,
+    pub name: Name,
 
     /// Location of administration
     pub sitz_verwaltung: Option<String>,
@@ -39,12 +43,33 @@ pub struct GemeindeverbandSchluessel {
 }
 
 impl GemeindeverbandSchluessel {
-    pub fn new(kreis: KreisSchluessel, gemeindeverband: u16) -> Self {
+    pub const fn new(kreis: KreisSchluessel, gemeindeverband: u16) -> Self {
         Self {
             kreis,
             gemeindeverband,
         }
     }
+
+    /// Creates a key from a literal (e.g. `"100410100"`). This is used by the
+    /// [`gemeindeverband!`](crate::gemeindeverband) macro.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not a valid key. In const context this is a compile
+    /// error.
+    pub const fn from_literal(s: &str) -> Self {
+        check_literal_length(s, 9);
+        Self::from_literal_digits(s)
+    }
+
+    /// Creates a key from the first 9 digits of `s`, without checking the
+    /// length.
+    pub(crate) const fn from_literal_digits(s: &str) -> Self {
+        Self::new(
+            KreisSchluessel::from_literal_digits(s),
+            literal_digits(s, 5, 9),
+        )
+    }
 }
 
 impl FromStr for GemeindeverbandSchluessel {
@@ -64,7 +89,7 @@ impl FromStr for GemeindeverbandSchluessel {
 
 impl Display for GemeindeverbandSchluessel {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}{:4}", self.kreis, self.gemeindeverband)
+        write!(f, "{}{:04}", self.kreis, self.gemeindeverband)
     }
 }
 
@@ -86,6 +111,7 @@ impl From<GemeindeverbandSchluessel> for LandSchluessel {
     }
 }
 
+/// Type of Gemeindeverband
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum GemeindeverbandTextkennzeichen {
     VerbandsfreieGemeinde,
@@ -117,3 +143,68 @@ impl TryFrom<u8> for GemeindeverbandTextkennzeichen {
         }
     }
 }
+
+impl From<GemeindeverbandTextkennzeichen> for u8 {
+    fn from(textkennzeichen: GemeindeverbandTextkennzeichen) -> Self {
+        textkennzeichen.code()
+    }
+}
+
+impl GemeindeverbandTextkennzeichen {
+    /// Returns the numeric code as used in the GV100AD file.
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::VerbandsfreieGemeinde => 50,
+            Self::Amt => 51,
+            Self::Samtgemeinde => 52,
+            Self::Verbandsgemeinde => 53,
+            Self::Verwaltungsgemeinschaft => 54,
+            Self::Kirchspielslandgemeinde => 55,
+            Self::Verwaltungsverband => 56,
+            Self::VGTraegermodell => 57,
+            Self::ErfuellendeGemeinde => 58,
+        }
+    }
+
+    /// Returns the official German label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::VerbandsfreieGemeinde => "Verbandsfreie Gemeinde",
+            Self::Amt => "Amt",
+            Self::Samtgemeinde => "Samtgemeinde",
+            Self::Verbandsgemeinde => "Verbandsgemeinde",
+            Self::Verwaltungsgemeinschaft => "Verwaltungsgemeinschaft",
+            Self::Kirchspielslandgemeinde => "Kirchspielslandgemeinde",
+            Self::Verwaltungsverband => "Verwaltungsverband",
+            Self::VGTraegermodell => "Verwaltungsgemeinschaft (Trägermodell)",
+            Self::ErfuellendeGemeinde => "Erfüllende Gemeinde",
+        }
+    }
+
+    /// Returns an English label.
+    pub fn label_en(&self) -> &'static str {
+        match self {
+            Self::VerbandsfreieGemeinde => "Municipality not belonging to an association",
+            Self::Amt => "Office",
+            Self::Samtgemeinde => "Collective municipality",
+            Self::Verbandsgemeinde => "Association municipality",
+            Self::Verwaltungsgemeinschaft => "Administrative community",
+            Self::Kirchspielslandgemeinde => "Parish municipality",
+            Self::Verwaltungsverband => "Administrative association",
+            Self::VGTraegermodell => "Administrative community (host model)",
+            Self::ErfuellendeGemeinde => "Fulfilling municipality",
+        }
+    }
+
+    /// Whether this is a single Gemeinde that doesn't belong to any
+    /// association.
+    pub fn is_verbandsfrei(&self) -> bool {
+        matches!(self, Self::VerbandsfreieGemeinde)
+    }
+}
+
+impl Display for GemeindeverbandTextkennzeichen {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
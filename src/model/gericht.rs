@@ -0,0 +1,339 @@
+//! Court districts (Gerichtsbezirke). Courts are only numbered uniquely within
+//! a Land, so the keys here always include the Land.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use crate::error::{Error, ParseKeyError};
+
+use super::land::LandSchluessel;
+
+fn parse_digits(s: &str) -> Result<u8, ParseKeyError> {
+    if !s.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(ParseKeyError::non_numeric(s));
+    }
+    s.parse().map_err(|_| ParseKeyError::non_numeric(s))
+}
+
+/// Key of an Oberlandesgericht (e.g. `101` for OLG 1 in Land 10).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct OberlandesgerichtSchluessel {
+    pub land: LandSchluessel,
+    pub oberlandesgericht: u8,
+}
+
+impl OberlandesgerichtSchluessel {
+    pub const fn new(land: LandSchluessel, oberlandesgericht: u8) -> Self {
+        Self {
+            land,
+            oberlandesgericht,
+        }
+    }
+}
+
+impl FromStr for OberlandesgerichtSchluessel {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 3 {
+            return Err(ParseKeyError::invalid_length(s, 3));
+        }
+        if !s.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(ParseKeyError::non_numeric(s));
+        }
+
+        let land = s[0..2].parse()?;
+        let oberlandesgericht = parse_digits(&s[2..])?;
+
+        Ok(Self::new(land, oberlandesgericht))
+    }
+}
+
+impl Display for OberlandesgerichtSchluessel {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}{:01}", self.land, self.oberlandesgericht)
+    }
+}
+
+impl From<OberlandesgerichtSchluessel> for LandSchluessel {
+    fn from(oberlandesgericht: OberlandesgerichtSchluessel) -> Self {
+        oberlandesgericht.land
+    }
+}
+
+/// Key of a Landgericht (e.g. `1011` for LG 1 in OLG 1 in Land 10).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct LandgerichtSchluessel {
+    pub oberlandesgericht: OberlandesgerichtSchluessel,
+    pub landgericht: u8,
+}
+
+impl LandgerichtSchluessel {
+    pub const fn new(oberlandesgericht: OberlandesgerichtSchluessel, landgericht: u8) -> Self {
+        Self {
+            oberlandesgericht,
+            landgericht,
+        }
+    }
+}
+
+impl FromStr for LandgerichtSchluessel {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 4 {
+            return Err(ParseKeyError::invalid_length(s, 4));
+        }
+        if !s.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(ParseKeyError::non_numeric(s));
+        }
+
+        let oberlandesgericht = s[0..3].parse()?;
+        let landgericht = parse_digits(&s[3..])?;
+
+        Ok(Self::new(oberlandesgericht, landgericht))
+    }
+}
+
+impl Display for LandgerichtSchluessel {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}{:01}", self.oberlandesgericht, self.landgericht)
+    }
+}
+
+impl From<LandgerichtSchluessel> for OberlandesgerichtSchluessel {
+    fn from(landgericht: LandgerichtSchluessel) -> Self {
+        landgericht.oberlandesgericht
+    }
+}
+
+impl From<LandgerichtSchluessel> for LandSchluessel {
+    fn from(landgericht: LandgerichtSchluessel) -> Self {
+        landgericht.oberlandesgericht.into()
+    }
+}
+
+/// Key of an Amtsgericht (e.g. `101109` for AG 09 in LG 1 in OLG 1 in Land
+/// 10).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct AmtsgerichtSchluessel {
+    pub landgericht: LandgerichtSchluessel,
+    pub amtsgericht: u8,
+}
+
+impl AmtsgerichtSchluessel {
+    pub const fn new(landgericht: LandgerichtSchluessel, amtsgericht: u8) -> Self {
+        Self {
+            landgericht,
+            amtsgericht,
+        }
+    }
+}
+
+impl FromStr for AmtsgerichtSchluessel {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 6 {
+            return Err(ParseKeyError::invalid_length(s, 6));
+        }
+        if !s.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(ParseKeyError::non_numeric(s));
+        }
+
+        let landgericht = s[0..4].parse()?;
+        let amtsgericht = parse_digits(&s[4..])?;
+
+        Ok(Self::new(landgericht, amtsgericht))
+    }
+}
+
+impl Display for AmtsgerichtSchluessel {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}{:02}", self.landgericht, self.amtsgericht)
+    }
+}
+
+impl From<AmtsgerichtSchluessel> for LandgerichtSchluessel {
+    fn from(amtsgericht: AmtsgerichtSchluessel) -> Self {
+        amtsgericht.landgericht
+    }
+}
+
+impl From<AmtsgerichtSchluessel> for OberlandesgerichtSchluessel {
+    fn from(amtsgericht: AmtsgerichtSchluessel) -> Self {
+        amtsgericht.landgericht.into()
+    }
+}
+
+impl From<AmtsgerichtSchluessel> for LandSchluessel {
+    fn from(amtsgericht: AmtsgerichtSchluessel) -> Self {
+        amtsgericht.landgericht.into()
+    }
+}
+
+/// Any court.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Gericht {
+    Oberlandesgericht(OberlandesgerichtSchluessel),
+    Landgericht(LandgerichtSchluessel),
+    Amtsgericht(AmtsgerichtSchluessel),
+}
+
+impl From<OberlandesgerichtSchluessel> for Gericht {
+    fn from(oberlandesgericht: OberlandesgerichtSchluessel) -> Self {
+        Self::Oberlandesgericht(oberlandesgericht)
+    }
+}
+
+impl From<LandgerichtSchluessel> for Gericht {
+    fn from(landgericht: LandgerichtSchluessel) -> Self {
+        Self::Landgericht(landgericht)
+    }
+}
+
+impl From<AmtsgerichtSchluessel> for Gericht {
+    fn from(amtsgericht: AmtsgerichtSchluessel) -> Self {
+        Self::Amtsgericht(amtsgericht)
+    }
+}
+
+impl FromStr for Gericht {
+    type Err = ParseKeyError;
+
+    /// Parses the key of an Oberlandesgericht (3 digits), Landgericht (4
+    /// digits) or Amtsgericht (6 digits). For other lengths, the error reports
+    /// the length of the next longer key.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.len() {
+            3 => Ok(Self::Oberlandesgericht(s.parse()?)),
+            4 => Ok(Self::Landgericht(s.parse()?)),
+            6 => Ok(Self::Amtsgericht(s.parse()?)),
+            0..=2 => Err(ParseKeyError::invalid_length(s, 3)),
+            _ => Err(ParseKeyError::invalid_length(s, 6)),
+        }
+    }
+}
+
+impl Display for Gericht {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Oberlandesgericht(k) => write!(f, "{}", k),
+            Self::Landgericht(k) => write!(f, "{}", k),
+            Self::Amtsgericht(k) => write!(f, "{}", k),
+        }
+    }
+}
+
+/// Information regarding juristical districts
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Gerichtbarkeit {
+    pub oberlandesgericht: OberlandesgerichtSchluessel,
+    pub landgericht: LandgerichtSchluessel,
+    pub amtsgericht: AmtsgerichtSchluessel,
+}
+
+impl Gerichtbarkeit {
+    /// Parses the court district field of a Gemeinde in Land `land`. The field
+    /// consists of 4 digits: Oberlandesgericht (1), Landgericht (1) and
+    /// Amtsgericht (2).
+    pub fn parse(s: &str, land: LandSchluessel) -> Result<Self, Error> {
+        let invalid = || Error::InvalidGerichtbarkeit(s.to_owned());
+
+        if s.len() != 4 || !s.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let oberlandesgericht =
+            OberlandesgerichtSchluessel::new(land, s[0..1].parse().map_err(|_| invalid())?);
+        let landgericht =
+            LandgerichtSchluessel::new(oberlandesgericht, s[1..2].parse().map_err(|_| invalid())?);
+        let amtsgericht =
+            AmtsgerichtSchluessel::new(landgericht, s[2..4].parse().map_err(|_| invalid())?);
+
+        Ok(Self::from(amtsgericht))
+    }
+}
+
+impl From<AmtsgerichtSchluessel> for Gerichtbarkeit {
+    fn from(amtsgericht: AmtsgerichtSchluessel) -> Self {
+        Self {
+            oberlandesgericht: amtsgericht.into(),
+            landgericht: amtsgericht.into(),
+            amtsgericht,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_gerichtbarkeit() {
+        let gerichtbarkeit = Gerichtbarkeit::parse("1109", LandSchluessel::new(10)).unwrap();
+        assert_eq!(gerichtbarkeit.oberlandesgericht.oberlandesgericht, 1);
+        assert_eq!(gerichtbarkeit.landgericht.landgericht, 1);
+        assert_eq!(gerichtbarkeit.amtsgericht.amtsgericht, 9);
+        assert_eq!(gerichtbarkeit.amtsgericht.to_string(), "101109");
+        assert_eq!(
+            gerichtbarkeit.amtsgericht,
+            "101109".parse::<AmtsgerichtSchluessel>().unwrap()
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_gerichtbarkeit() {
+        let land = LandSchluessel::new(10);
+        assert!(Gerichtbarkeit::parse("110", land).is_err());
+        assert!(Gerichtbarkeit::parse("11 9", land).is_err());
+        assert!(Gerichtbarkeit::parse("", land).is_err());
+    }
+
+    #[test]
+    fn it_parses_court_keys_of_any_level() {
+        assert!(matches!(
+            "101".parse::<Gericht>(),
+            Ok(Gericht::Oberlandesgericht(_))
+        ));
+        assert!(matches!(
+            "1011".parse::<Gericht>(),
+            Ok(Gericht::Landgericht(_))
+        ));
+        assert!(matches!(
+            "101109".parse::<Gericht>(),
+            Ok(Gericht::Amtsgericht(_))
+        ));
+
+        let expected = |s: &str| match s.parse::<Gericht>() {
+            Err(ParseKeyError::InvalidLength { expected, .. }) => expected,
+            result => panic!("unexpected result: {:?}", result),
+        };
+        assert_eq!(expected("10"), 3);
+        assert_eq!(expected("10110"), 6);
+        assert_eq!(expected("1011091"), 6);
+    }
+
+    #[test]
+    fn it_rejects_non_ascii_court_keys() {
+        // The lengths are in bytes, so these would be sliced inside a char.
+        assert!(matches!(
+            "1é".parse::<OberlandesgerichtSchluessel>(),
+            Err(ParseKeyError::NonNumeric(_))
+        ));
+        assert!(matches!(
+            "10é".parse::<LandgerichtSchluessel>(),
+            Err(ParseKeyError::NonNumeric(_))
+        ));
+        assert!(matches!(
+            "1011é".parse::<AmtsgerichtSchluessel>(),
+            Err(ParseKeyError::NonNumeric(_))
+        ));
+        assert!(matches!(
+            "1é".parse::<Gericht>(),
+            Err(ParseKeyError::NonNumeric(_))
+        ));
+    }
+}
@@ -6,9 +6,12 @@ use std::{
 
 use chrono::NaiveDate;
 
-use crate::error::{Error, ParseKeyError};
+use crate::{
+    error::{Error, ParseKeyError},
+    macros::{check_literal_length, literal_digits},
+};
 
-use super::{land::LandSchluessel, regierungsbezirk::RegierungsbezirkSchluessel};
+use super::{land::LandSchluessel, name::Name, regierungsbezirk::RegierungsbezirkSchluessel};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct KreisSchluessel {
@@ -17,7 +20,7 @@ pub struct KreisSchluessel {
 }
 
 impl KreisSchluessel {
-    pub fn new(regierungsbezirk: RegierungsbezirkSchluessel, kreis: u8) -> Self {
+    pub const fn new(regierungsbezirk: RegierungsbezirkSchluessel, kreis: u8) -> Self {
         Self {
             regierungsbezirk,
             kreis,
@@ -26,12 +29,33 @@ impl KreisSchluessel {
 
     /// Creates a Kreisschluessel directly from the parent Landschluessel and
     /// the Kreis identifier. This sets the Regierungsbezirk part to 0.
-    pub fn new_land(land: LandSchluessel, kreis: u8) -> Self {
+    pub const fn new_land(land: LandSchluessel, kreis: u8) -> Self {
         Self {
             regierungsbezirk: RegierungsbezirkSchluessel::new(land, 0),
             kreis,
         }
     }
+
+    /// Creates a key from a literal (e.g. `"10041"`). This is used by the
+    /// [`kreis!`](crate::kreis) macro.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not a valid key. In const context this is a compile
+    /// error.
+    pub const fn from_literal(s: &str) -> Self {
+        check_literal_length(s, 5);
+        Self::from_literal_digits(s)
+    }
+
+    /// Creates a key from the first 5 digits of `s`, without checking the
+    /// length.
+    pub(crate) const fn from_literal_digits(s: &str) -> Self {
+        Self::new(
+            RegierungsbezirkSchluessel::from_literal_digits(s),
+            literal_digits(s, 3, 5) as u8,
+        )
+    }
 }
 
 impl FromStr for KreisSchluessel {
@@ -51,7 +75,7 @@ impl FromStr for KreisSchluessel {
 
 impl Display for KreisSchluessel {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}{:2}", self.regierungsbezirk, self.kreis)
+        write!(f, "{}{:02}", self.regierungsbezirk, self.kreis)
     }
 }
 
@@ -77,7 +101,7 @@ pub struct KreisDaten {
     pub schluessel: KreisSchluessel,
 
     /// Name of Kreis
-    pub name: String,
+    pub name: Name,
 
     /// Location of administration
     pub sitz_verwaltung: String,
@@ -86,6 +110,7 @@ pub struct KreisDaten {
     pub textkennzeichen: KreisTextkennzeichen,
 }
 
+/// Type of Kreis
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum KreisTextkennzeichen {
     KreisfreieStadt,
@@ -109,3 +134,55 @@ impl TryFrom<u8> for KreisTextkennzeichen {
         }
     }
 }
+
+impl From<KreisTextkennzeichen> for u8 {
+    fn from(textkennzeichen: KreisTextkennzeichen) -> Self {
+        textkennzeichen.code()
+    }
+}
+
+impl KreisTextkennzeichen {
+    /// Returns the numeric code as used in the GV100AD file.
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::KreisfreieStadt => 41,
+            Self::Stadtkreis => 42,
+            Self::Kreis => 43,
+            Self::Landkreis => 44,
+            Self::Regionalverband => 45,
+        }
+    }
+
+    /// Returns the official German label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::KreisfreieStadt => "Kreisfreie Stadt",
+            Self::Stadtkreis => "Stadtkreis",
+            Self::Kreis => "Kreis",
+            Self::Landkreis => "Landkreis",
+            Self::Regionalverband => "Regionalverband",
+        }
+    }
+
+    /// Returns an English label.
+    pub fn label_en(&self) -> &'static str {
+        match self {
+            Self::KreisfreieStadt => "District-free city",
+            Self::Stadtkreis => "Urban district",
+            Self::Kreis => "District",
+            Self::Landkreis => "Rural district",
+            Self::Regionalverband => "Regional association",
+        }
+    }
+
+    /// Whether the Kreis is a city (i.e. a Kreisfreie Stadt or Stadtkreis).
+    pub fn is_city(&self) -> bool {
+        matches!(self, Self::KreisfreieStadt | Self::Stadtkreis)
+    }
+}
+
+impl Display for KreisTextkennzeichen {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
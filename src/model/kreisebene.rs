@@ -0,0 +1,43 @@
+//! District level (Kreisebene). This treats Kreise and kreisfreie Staedte
+//! alike, which appear in the GV100AD file as Kreis, Gemeindeverband and
+//! Gemeinde at once.
+
+use super::{
+    gemeinde::GemeindeDaten,
+    kreis::{KreisDaten, KreisSchluessel, KreisTextkennzeichen},
+    name::Name,
+    statistics::Statistics,
+};
+
+/// A unit of the district level: a Kreis or a kreisfreie Stadt.
+#[derive(Clone, Debug)]
+pub struct Kreisebene<'a> {
+    /// The Kreis record
+    pub kreis: &'a KreisDaten,
+
+    /// The Gemeinde record, if this is a kreisfreie Stadt
+    pub stadt: Option<&'a GemeindeDaten>,
+
+    /// Area and population, rolled up from the Gemeinden
+    pub statistics: Statistics,
+}
+
+impl<'a> Kreisebene<'a> {
+    pub fn schluessel(&self) -> KreisSchluessel {
+        self.kreis.schluessel
+    }
+
+    pub fn name(&self) -> &'a Name {
+        &self.kreis.name
+    }
+
+    /// Type of the unit (e.g. Landkreis or kreisfreie Stadt)
+    pub fn textkennzeichen(&self) -> KreisTextkennzeichen {
+        self.kreis.textkennzeichen
+    }
+
+    /// Whether this is a kreisfreie Stadt (or Stadtkreis).
+    pub fn is_kreisfreie_stadt(&self) -> bool {
+        self.kreis.textkennzeichen.is_city()
+    }
+}
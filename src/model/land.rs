@@ -5,7 +5,12 @@ use std::{
 
 use chrono::NaiveDate;
 
-use crate::error::ParseKeyError;
+use crate::{
+    error::ParseKeyError,
+    macros::{check_literal_length, literal_digits},
+};
+
+use super::name::Name;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct LandSchluessel {
@@ -13,9 +18,27 @@ pub struct LandSchluessel {
 }
 
 impl LandSchluessel {
-    pub fn new(land: u8) -> Self {
+    pub const fn new(land: u8) -> Self {
         Self { land }
     }
+
+    /// Creates a key from a literal (e.g. `"10"`). This is used by the
+    /// [`land!`](crate::land) macro.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not a valid key. In const context this is a compile
+    /// error.
+    pub const fn from_literal(s: &str) -> Self {
+        check_literal_length(s, 2);
+        Self::from_literal_digits(s)
+    }
+
+    /// Creates a key from the first 2 digits of `s`, without checking the
+    /// length.
+    pub(crate) const fn from_literal_digits(s: &str) -> Self {
+        Self::new(literal_digits(s, 0, 2) as u8)
+    }
 }
 
 impl FromStr for LandSchluessel {
@@ -48,7 +71,7 @@ pub struct LandDaten {
     pub schluessel: LandSchluessel,
 
     /// Name of Land (e.g. `Saarland`)
-    pub name: String,
+    pub name: Name,
 
     /// Location of the government of this state.
     pub sitz_regierung: String,
@@ -1,7 +1,24 @@
+pub mod bezirk;
+pub mod classification;
 pub mod datensatz;
 pub mod gemeinde;
+pub mod gemeindefrei;
 pub mod gemeindeverband;
+pub mod gericht;
 pub mod kreis;
+pub mod kreisebene;
 pub mod land;
+pub mod name;
+pub mod official_name;
+pub mod packed;
+pub mod phonetik;
+pub mod plz;
+pub mod record;
 pub mod regierungsbezirk;
 pub mod region;
+pub mod search;
+pub mod slug;
+pub mod stadtstaat;
+pub mod statistics;
+pub mod verwaltung;
+pub mod wahlkreis;
@@ -0,0 +1,566 @@
+use std::{
+    convert::Infallible,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use super::land::LandSchluessel;
+
+/// Prefix used for units that don't exist anymore (e.g. `früher: Reg.-Bez.
+/// Trier`).
+const FORMER_PREFIX: &str = "früher:";
+
+/// Designations that are prepended to the name (e.g. `Landkreis Rostock`).
+const PREFIXES: &[(&str, Designation)] = &[
+    ("Reg.-Bez.", Designation::Regierungsbezirk),
+    ("Regierungsbezirk", Designation::Regierungsbezirk),
+    ("Regionalverband", Designation::Regionalverband),
+    ("Region", Designation::Region),
+    ("Städteregion", Designation::Staedteregion),
+    ("Landkreis", Designation::Landkreis),
+    ("Kreis", Designation::Kreis),
+];
+
+/// Officially bilingual names in the Sorbian settlement area, as Land, German
+/// name and minority-language name. Other names containing a `/` are German
+/// names with a regional suffix (e.g. `Schöneck/Vogtl.` or `Neuhausen/Spree`),
+/// so only names listed here are split. Frisian and Danish names (e.g. in
+/// Nordfriesland) are only used on signs and don't occur in GV100AD names.
+const BILINGUAL_NAMES: &[(u8, &str, &str)] = &[
+    // Brandenburg (Lower Sorbian)
+    (12, "Cottbus", "Chóśebuz"),
+    (12, "Spree-Neiße", "Sprjewja-Nysa"),
+    (12, "Alt Zauche-Wußwerk", "Stara Niwa-Wózwjerch"),
+    (12, "Briesen", "Brjazyna"),
+    (12, "Burg (Spreewald)", "Bórkowy (Błota)"),
+    (12, "Byhleguhre-Byhlen", "Běła Góra-Bělin"),
+    (12, "Dissen-Striesow", "Dešank-Strjažow"),
+    (12, "Drachhausen", "Hochoza"),
+    (12, "Drebkau", "Drjowk"),
+    (12, "Drehnow", "Drjenow"),
+    (12, "Felixsee", "Feliksowy Jazor"),
+    (12, "Guhrow", "Gory"),
+    (12, "Heinersbrück", "Móst"),
+    (12, "Jänschwalde", "Janšojce"),
+    (12, "Kolkwitz", "Gołkojce"),
+    (12, "Lübben (Spreewald)", "Lubin (Błota)"),
+    (12, "Lübbenau/Spreewald", "Lubnjow/Błota"),
+    (12, "Neu Zauche", "Nowa Niwa"),
+    (12, "Peitz", "Picnjo"),
+    (12, "Schlepzig", "Słopišća"),
+    (12, "Schmogrow-Fehrow", "Smogorjow-Prjawoz"),
+    (12, "Spremberg", "Grodk"),
+    (12, "Straupitz (Spreewald)", "Tšupc (Błota)"),
+    (12, "Tauer", "Turjej"),
+    (12, "Teichland", "Gatojce"),
+    (12, "Turnow-Preilack", "Turnow-Pśiłuk"),
+    (12, "Vetschau/Spreewald", "Wětošow/Błota"),
+    (12, "Welzow", "Wjelcej"),
+    (12, "Werben", "Wjerbno"),
+    (12, "Wiesengrund", "Łukojce"),
+    // Sachsen (Upper Sorbian)
+    (14, "Bad Muskau", "Mužakow"),
+    (14, "Bautzen", "Budyšin"),
+    (14, "Boxberg/O.L.", "Hamor"),
+    (14, "Burkau", "Porchow"),
+    (14, "Crostwitz", "Chrósćicy"),
+    (14, "Doberschau-Gaußig", "Dobruša-Huska"),
+    (14, "Elsterheide", "Halštrowska Hola"),
+    (14, "Elstra", "Halštrow"),
+    (14, "Göda", "Hodźij"),
+    (14, "Groß Düben", "Dźěwin"),
+    (14, "Großdubrau", "Wulka Dubrawa"),
+    (14, "Großpostwitz/O.L.", "Budestecy"),
+    (14, "Hochkirch", "Bukecy"),
+    (14, "Hoyerswerda", "Wojerecy"),
+    (14, "Kamenz", "Kamjenc"),
+    (14, "Königswartha", "Rakecy"),
+    (14, "Kubschütz", "Kubšicy"),
+    (14, "Lohsa", "Łaz"),
+    (14, "Malschwitz", "Malešecy"),
+    (14, "Nebelschütz", "Njebjelčicy"),
+    (14, "Neschwitz", "Njeswačidło"),
+    (14, "Obergurig", "Hornja Hórka"),
+    (14, "Panschwitz-Kuckau", "Pančicy-Kukow"),
+    (14, "Puschwitz", "Bóšicy"),
+    (14, "Räckelwitz", "Worklecy"),
+    (14, "Radibor", "Radwor"),
+    (14, "Ralbitz-Rosenthal", "Ralbicy-Róžant"),
+    (14, "Rietschen", "Rěčicy"),
+    (14, "Schleife", "Slepo"),
+    (14, "Spreetal", "Sprjewiny Doł"),
+    (14, "Trebendorf", "Trjebin"),
+    (14, "Weißkeißel", "Wuskidź"),
+    (14, "Weißwasser/O.L.", "Běła Woda"),
+    (14, "Wittichenau", "Kulow"),
+];
+
+/// Designation of a unit that is part of its official name.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Designation {
+    Stadt,
+    Landeshauptstadt,
+    Kreisstadt,
+    GrosseKreisstadt,
+    Hansestadt,
+    FreieHansestadt,
+    FreieUndHansestadt,
+    Universitaetsstadt,
+    Wissenschaftsstadt,
+    Markt,
+    Flecken,
+    Regierungsbezirk,
+    Region,
+    Regionalverband,
+    Staedteregion,
+    Landkreis,
+    Kreis,
+
+    /// Any other designation following the name (e.g. `Kurort`).
+    Other(String),
+}
+
+impl Designation {
+    /// Returns the designation for a label as used in the GV100AD file, e.g.
+    /// `Landeshauptstadt`.
+    pub fn from_label(label: &str) -> Option<Self> {
+        let designation = match label {
+            "Stadt" => Self::Stadt,
+            "Landeshauptstadt" => Self::Landeshauptstadt,
+            "Kreisstadt" => Self::Kreisstadt,
+            "Große Kreisstadt" => Self::GrosseKreisstadt,
+            "Hansestadt" => Self::Hansestadt,
+            "Freie Hansestadt" => Self::FreieHansestadt,
+            "Freie und Hansestadt" => Self::FreieUndHansestadt,
+            "Universitätsstadt" => Self::Universitaetsstadt,
+            "Wissenschaftsstadt" => Self::Wissenschaftsstadt,
+            "Markt" => Self::Markt,
+            "Flecken" => Self::Flecken,
+            "Reg.-Bez." | "Regierungsbezirk" => Self::Regierungsbezirk,
+            "Region" => Self::Region,
+            "Regionalverband" => Self::Regionalverband,
+            "Städteregion" => Self::Staedteregion,
+            "Landkreis" => Self::Landkreis,
+            "Kreis" => Self::Kreis,
+            _ => return None,
+        };
+        Some(designation)
+    }
+
+    /// Returns the label of the designation as used in the GV100AD file.
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Stadt => "Stadt",
+            Self::Landeshauptstadt => "Landeshauptstadt",
+            Self::Kreisstadt => "Kreisstadt",
+            Self::GrosseKreisstadt => "Große Kreisstadt",
+            Self::Hansestadt => "Hansestadt",
+            Self::FreieHansestadt => "Freie Hansestadt",
+            Self::FreieUndHansestadt => "Freie und Hansestadt",
+            Self::Universitaetsstadt => "Universitätsstadt",
+            Self::Wissenschaftsstadt => "Wissenschaftsstadt",
+            Self::Markt => "Markt",
+            Self::Flecken => "Flecken",
+            Self::Regierungsbezirk => "Reg.-Bez.",
+            Self::Region => "Region",
+            Self::Regionalverband => "Regionalverband",
+            Self::Staedteregion => "Städteregion",
+            Self::Landkreis => "Landkreis",
+            Self::Kreis => "Kreis",
+            Self::Other(label) => label,
+        }
+    }
+
+    /// Whether the designation is a city designation (e.g. `Stadt` or
+    /// `Landeshauptstadt`).
+    pub fn is_city(&self) -> bool {
+        matches!(
+            self,
+            Self::Stadt
+                | Self::Landeshauptstadt
+                | Self::Kreisstadt
+                | Self::GrosseKreisstadt
+                | Self::Hansestadt
+                | Self::FreieHansestadt
+                | Self::FreieUndHansestadt
+                | Self::Universitaetsstadt
+                | Self::Wissenschaftsstadt
+        )
+    }
+
+    /// Whether the designation precedes the name (e.g. `Landkreis Rostock`).
+    pub fn is_prefix(&self) -> bool {
+        matches!(
+            self,
+            Self::Regierungsbezirk
+                | Self::Region
+                | Self::Regionalverband
+                | Self::Staedteregion
+                | Self::Landkreis
+                | Self::Kreis
+        )
+    }
+}
+
+impl Display for Designation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// Language of a name.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Language {
+    German,
+    LowerSorbian,
+    UpperSorbian,
+}
+
+impl Language {
+    /// Returns the minority language of `name` if it is the minority-language
+    /// part of an officially bilingual name in the Land (e.g. `Chóśebuz` in
+    /// Brandenburg).
+    pub fn minority(land: LandSchluessel, name: &str) -> Option<Self> {
+        BILINGUAL_NAMES
+            .iter()
+            .find(|(l, _, minority)| *l == land.land && *minority == name)
+            .and_then(|(l, _, _)| match l {
+                12 => Some(Self::LowerSorbian),
+                14 => Some(Self::UpperSorbian),
+                _ => None,
+            })
+    }
+
+    /// Returns the ISO 639 code of the language (e.g. `dsb` for Lower
+    /// Sorbian).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::German => "de",
+            Self::LowerSorbian => "dsb",
+            Self::UpperSorbian => "hsb",
+        }
+    }
+
+    /// Returns the German label of the language.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::German => "Deutsch",
+            Self::LowerSorbian => "Niedersorbisch",
+            Self::UpperSorbian => "Obersorbisch",
+        }
+    }
+
+    /// Returns an English label of the language.
+    pub fn label_en(&self) -> &'static str {
+        match self {
+            Self::German => "German",
+            Self::LowerSorbian => "Lower Sorbian",
+            Self::UpperSorbian => "Upper Sorbian",
+        }
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// A name in a specific language.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LocalizedName {
+    pub language: Language,
+    pub name: String,
+}
+
+/// An official name of a unit, e.g. `Saarbrücken, Landeshauptstadt`.
+///
+/// The name is split into the base name (`Saarbrücken`) and the designation
+/// (`Landeshauptstadt`). The designation can either follow the base name
+/// separated by a comma, or precede it (e.g. `Reg.-Bez. Trier`). Names of
+/// units that don't exist anymore are prefixed with `früher:`.
+///
+/// Units in the Sorbian settlement area can have a bilingual name (e.g.
+/// `Cottbus/Chóśebuz, Stadt`). For these the base name is the German name, and
+/// the Sorbian name is stored separately. No other minority language occurs in
+/// GV100AD names.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Name {
+    /// The full name as in the GV100AD file.
+    pub full: String,
+
+    /// The (German) name without designation (e.g. `Saarbrücken`).
+    pub base: String,
+
+    /// The name in a minority language (e.g. `Chóśebuz`).
+    pub minority: Option<LocalizedName>,
+
+    /// The designation (e.g. `Landeshauptstadt`).
+    pub designation: Option<Designation>,
+
+    /// Whether the unit doesn't exist anymore (i.e. name is prefixed with
+    /// `früher:`).
+    pub former: bool,
+}
+
+impl Name {
+    /// Parses a name as found in the GV100AD file.
+    pub fn parse(s: &str) -> Self {
+        let full = s.trim();
+
+        let (s, former) = match full.strip_prefix(FORMER_PREFIX) {
+            Some(s) => (s.trim_start(), true),
+            None => (full, false),
+        };
+
+        let (base, designation) = split_designation(s);
+
+        Self {
+            full: full.to_owned(),
+            base: base.to_owned(),
+            minority: None,
+            designation,
+            former,
+        }
+    }
+
+    /// Parses a name of a unit in the Land `land`. Officially bilingual names
+    /// (e.g. `Bautzen/Budyšin`) are split into the German and
+    /// minority-language name.
+    pub fn parse_in_land(s: &str, land: LandSchluessel) -> Self {
+        let mut name = Self::parse(s);
+
+        let bilingual = BILINGUAL_NAMES.iter().find(|(l, german, minority)| {
+            *l == land.land
+                && name
+                    .base
+                    .strip_prefix(german)
+                    .and_then(|rest| rest.strip_prefix('/'))
+                    == Some(minority)
+        });
+
+        if let Some((_, german, minority)) = bilingual {
+            if let Some(language) = Language::minority(land, minority) {
+                name.minority = Some(LocalizedName {
+                    language,
+                    name: (*minority).to_owned(),
+                });
+                name.base = (*german).to_owned();
+            }
+        }
+
+        name
+    }
+
+    /// Returns the German name without designation.
+    pub fn german(&self) -> &str {
+        &self.base
+    }
+
+    /// Returns the name without designation, unless the designation tells a
+    /// Kreis apart from a kreisfreie Stadt of the same name (e.g. `Landkreis
+    /// Rostock` and `Rostock, Hansestadt`). Used for slugs and display names.
+    pub fn short(&self) -> String {
+        match &self.designation {
+            Some(
+                designation @ (Designation::Regionalverband
+                | Designation::Staedteregion
+                | Designation::Landkreis
+                | Designation::Kreis),
+            ) => format!("{} {}", designation.label(), self.base),
+            _ => self.base.clone(),
+        }
+    }
+
+    /// Returns all names without designation, each tagged with its language.
+    /// The German name is always first.
+    pub fn localized(&self) -> Vec<LocalizedName> {
+        let mut names = vec![LocalizedName {
+            language: Language::German,
+            name: self.base.clone(),
+        }];
+        names.extend(self.minority.clone());
+        names
+    }
+
+    /// Whether `s` matches this name, i.e. is equal to the full name, or the
+    /// German or minority-language name (ignoring case).
+    pub fn matches(&self, s: &str) -> bool {
+        let s = s.trim().to_lowercase();
+        self.full.to_lowercase() == s
+            || self.base.to_lowercase() == s
+            || self
+                .minority
+                .as_ref()
+                .is_some_and(|minority| minority.name.to_lowercase() == s)
+    }
+
+    /// Returns the full name.
+    pub fn as_str(&self) -> &str {
+        &self.full
+    }
+}
+
+fn split_designation(s: &str) -> (&str, Option<Designation>) {
+    for (prefix, designation) in PREFIXES {
+        if let Some(base) = s.strip_prefix(prefix) {
+            if base.starts_with(' ') && !base.trim().is_empty() {
+                return (base.trim(), Some(designation.clone()));
+            }
+        }
+    }
+
+    if let Some((base, label)) = s.split_once(", ") {
+        let label = label.trim();
+        let designation =
+            Designation::from_label(label).unwrap_or_else(|| Designation::Other(label.to_owned()));
+        return (base.trim(), Some(designation));
+    }
+
+    (s, None)
+}
+
+impl FromStr for Name {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::parse(s))
+    }
+}
+
+impl From<&str> for Name {
+    fn from(s: &str) -> Self {
+        Self::parse(s)
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        &self.full
+    }
+}
+
+impl Display for Name {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.full)
+    }
+}
+
+impl PartialEq<str> for Name {
+    fn eq(&self, other: &str) -> bool {
+        self.full == other
+    }
+}
+
+impl PartialEq<&str> for Name {
+    fn eq(&self, other: &&str) -> bool {
+        self.full == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_plain_names() {
+        let name = Name::parse("Beckingen");
+        assert_eq!(name.base, "Beckingen");
+        assert_eq!(name.designation, None);
+        assert!(!name.former);
+    }
+
+    #[test]
+    fn it_parses_suffix_designations() {
+        let name = Name::parse("Saarbrücken, Landeshauptstadt");
+        assert_eq!(name, "Saarbrücken, Landeshauptstadt");
+        assert_eq!(name.base, "Saarbrücken");
+        assert_eq!(name.designation, Some(Designation::Landeshauptstadt));
+
+        let name = Name::parse("Merzig, Kreisstadt");
+        assert_eq!(name.base, "Merzig");
+        assert_eq!(name.designation, Some(Designation::Kreisstadt));
+
+        let name = Name::parse("Bad Elster, Kurort");
+        assert_eq!(name.base, "Bad Elster");
+        assert_eq!(
+            name.designation,
+            Some(Designation::Other("Kurort".to_owned()))
+        );
+    }
+
+    #[test]
+    fn it_parses_prefix_designations() {
+        let name = Name::parse("Regionalverband Saarbrücken");
+        assert_eq!(name.base, "Saarbrücken");
+        assert_eq!(name.designation, Some(Designation::Regionalverband));
+
+        let name = Name::parse("Kreisfreie Stadt");
+        assert_eq!(name.base, "Kreisfreie Stadt");
+        assert_eq!(name.designation, None);
+    }
+
+    #[test]
+    fn it_parses_bilingual_names() {
+        let land = LandSchluessel::new(12);
+        let name = Name::parse_in_land("Cottbus/Chóśebuz, Stadt", land);
+        assert_eq!(name.full, "Cottbus/Chóśebuz, Stadt");
+        assert_eq!(name.german(), "Cottbus");
+        assert_eq!(
+            name.minority,
+            Some(LocalizedName {
+                language: Language::LowerSorbian,
+                name: "Chóśebuz".to_owned(),
+            })
+        );
+        assert_eq!(name.designation, Some(Designation::Stadt));
+        assert!(name.matches("cottbus"));
+        assert!(name.matches("Chóśebuz"));
+
+        let name = Name::parse_in_land("Lübbenau/Spreewald/Lubnjow/Błota, Stadt", land);
+        assert_eq!(name.german(), "Lübbenau/Spreewald");
+        assert_eq!(name.minority.unwrap().name, "Lubnjow/Błota");
+
+        let land = LandSchluessel::new(14);
+        let name = Name::parse_in_land("Bautzen/Budyšin", land);
+        assert_eq!(name.minority.unwrap().language, Language::UpperSorbian);
+
+        let name = Name::parse_in_land("Weißwasser/O.L./Běła Woda, Stadt", land);
+        assert_eq!(name.german(), "Weißwasser/O.L.");
+        assert_eq!(name.minority.unwrap().name, "Běła Woda");
+
+        let name = Name::parse_in_land("Aach/Hegau", LandSchluessel::new(8));
+        assert_eq!(name.base, "Aach/Hegau");
+        assert_eq!(name.minority, None);
+    }
+
+    #[test]
+    fn it_keeps_regional_suffixes() {
+        for (s, land) in [
+            ("Neuhausen/Spree", 12),
+            ("Schwarzheide/Lausitz", 12),
+            ("Schöneck/Vogtl., Stadt", 14),
+            ("Falkenstein/Vogtl., Stadt", 14),
+            ("Oberwiesenthal/Erzgeb., Stadt", 14),
+            ("Boxberg/O.L.", 14),
+            ("Friedrichstadt/Eider", 1),
+        ]
+        .iter()
+        {
+            let name = Name::parse_in_land(s, LandSchluessel::new(*land));
+            assert_eq!(name.minority, None, "{}", s);
+            assert!(name.base.contains('/'), "{}", s);
+        }
+        assert_eq!(Language::minority(LandSchluessel::new(14), "Vogtl."), None);
+        assert_eq!(Language::minority(LandSchluessel::new(12), "Spree"), None);
+    }
+
+    #[test]
+    fn it_parses_former_names() {
+        let name = Name::parse("früher: Reg.-Bez. Trier");
+        assert_eq!(name.full, "früher: Reg.-Bez. Trier");
+        assert_eq!(name.base, "Trier");
+        assert_eq!(name.designation, Some(Designation::Regierungsbezirk));
+        assert!(name.former);
+    }
+}
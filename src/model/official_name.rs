@@ -0,0 +1,376 @@
+//! Official designations combining the type of a unit and its name, e.g.
+//! `Landkreis Merzig-Wadern` or `des Amtes Büchen`.
+
+use std::fmt::{self, Display, Formatter};
+
+use super::{
+    gemeinde::{GemeindeDaten, GemeindeTextkennzeichen},
+    gemeindeverband::{GemeindeverbandDaten, GemeindeverbandTextkennzeichen},
+    kreis::{KreisDaten, KreisTextkennzeichen},
+    name::{Designation, Name},
+};
+
+/// Title of a unit (e.g. `Landkreis`) in nominative and genitive case.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Title {
+    /// Nominative, e.g. `Landkreis`
+    pub nominative: &'static str,
+
+    /// Genitive including the article, e.g. `des Landkreises`
+    pub genitive: &'static str,
+}
+
+impl Title {
+    const fn new(nominative: &'static str, genitive: &'static str) -> Self {
+        Self {
+            nominative,
+            genitive,
+        }
+    }
+}
+
+/// Form of an official name.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Form {
+    /// Only the name, e.g. `Merzig-Wadern`
+    Short,
+
+    /// Title and name, e.g. `Landkreis Merzig-Wadern`
+    Long,
+
+    /// Title and name in genitive case, e.g. `des Landkreises Merzig-Wadern`
+    Genitive,
+}
+
+/// Kind of unit, which decides how designations in the name are used.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Unit {
+    /// A Kreis. Only prefix designations (e.g. `Regionalverband`) are used.
+    Kreis,
+
+    /// A Gemeindeverband that is an association of Gemeinden (e.g. an Amt).
+    /// Its name can start with the title (e.g. `Amt Büchen`).
+    Verband,
+
+    /// A Gemeinde. City designations more specific than `Stadt` (e.g.
+    /// `Landeshauptstadt`) are used too.
+    Gemeinde,
+
+    /// A verbandsfreie or erfüllende Gemeinde as Gemeindeverband. Its
+    /// Textkennzeichen doesn't tell whether it's a Stadt, so any designation
+    /// with a title is used (e.g. `Stadt` or `Markt`).
+    EinzelGemeinde,
+}
+
+/// Official name of a unit, combining its title and name.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OfficialName<'a> {
+    pub title: Title,
+
+    /// Name without the title, if the name starts with it (e.g. `Wehlen` for
+    /// `Stadt Wehlen`).
+    pub name: &'a str,
+
+    short: &'a str,
+}
+
+impl<'a> OfficialName<'a> {
+    /// Creates the official name from a name and the title derived from the
+    /// Textkennzeichen.
+    ///
+    /// If the name already contains a title (e.g. `Regionalverband
+    /// Saarbrücken`), that title is used instead. Which other designations in
+    /// the name are used depends on the kind of unit (see [`Unit`]).
+    fn new(name: &'a Name, title: Title, unit: Unit) -> Self {
+        let title = match &name.designation {
+            Some(designation) if designation.is_prefix() => designation.title(),
+            Some(designation) if unit == Unit::Gemeinde && designation.is_specific_city() => {
+                designation.title()
+            }
+            Some(designation) if unit == Unit::EinzelGemeinde => designation.title(),
+            _ => None,
+        }
+        .unwrap_or(title);
+
+        // Don't repeat the title, if the name already starts with it (e.g.
+        // `Amt Büchen` or `Stadt Wehlen`). For Gemeinden it's part of the
+        // actual name though, so it's kept in the short form.
+        let base = name.base.as_str();
+        let untitled = base
+            .strip_prefix(title.nominative)
+            .and_then(|s| s.strip_prefix(' '))
+            .filter(|s| !s.is_empty())
+            .unwrap_or(base);
+        let short = match unit {
+            Unit::Verband => untitled,
+            _ => base,
+        };
+
+        Self {
+            title,
+            name: untitled,
+            short,
+        }
+    }
+
+    /// Returns only the name, e.g. `Merzig-Wadern`.
+    pub fn short(&self) -> &'a str {
+        self.short
+    }
+
+    /// Returns title and name, e.g. `Landkreis Merzig-Wadern`.
+    pub fn long(&self) -> String {
+        format!("{} {}", self.title.nominative, self.name)
+    }
+
+    /// Returns title and name in genitive case, e.g. `des Landkreises
+    /// Merzig-Wadern`.
+    pub fn genitive(&self) -> String {
+        format!("{} {}", self.title.genitive, self.name)
+    }
+
+    /// Formats the official name in the given form.
+    pub fn format(&self, form: Form) -> String {
+        match form {
+            Form::Short => self.short().to_owned(),
+            Form::Long => self.long(),
+            Form::Genitive => self.genitive(),
+        }
+    }
+}
+
+impl<'a> Display for OfficialName<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.title.nominative, self.name)
+    }
+}
+
+impl Designation {
+    fn is_specific_city(&self) -> bool {
+        self.is_city() && *self != Self::Stadt
+    }
+
+    /// Returns the title for this designation, if it has one.
+    pub fn title(&self) -> Option<Title> {
+        let title = match self {
+            Self::Stadt => Title::new("Stadt", "der Stadt"),
+            Self::Landeshauptstadt => Title::new("Landeshauptstadt", "der Landeshauptstadt"),
+            Self::Kreisstadt => Title::new("Kreisstadt", "der Kreisstadt"),
+            Self::GrosseKreisstadt => Title::new("Große Kreisstadt", "der Großen Kreisstadt"),
+            Self::Hansestadt => Title::new("Hansestadt", "der Hansestadt"),
+            Self::FreieHansestadt => Title::new("Freie Hansestadt", "der Freien Hansestadt"),
+            Self::FreieUndHansestadt => {
+                Title::new("Freie und Hansestadt", "der Freien und Hansestadt")
+            }
+            Self::Universitaetsstadt => Title::new("Universitätsstadt", "der Universitätsstadt"),
+            Self::Wissenschaftsstadt => Title::new("Wissenschaftsstadt", "der Wissenschaftsstadt"),
+            Self::Markt => Title::new("Markt", "des Marktes"),
+            Self::Flecken => Title::new("Flecken", "des Fleckens"),
+            Self::Regierungsbezirk => Title::new("Regierungsbezirk", "des Regierungsbezirks"),
+            Self::Region => Title::new("Region", "der Region"),
+            Self::Regionalverband => Title::new("Regionalverband", "des Regionalverbandes"),
+            Self::Staedteregion => Title::new("Städteregion", "der Städteregion"),
+            Self::Landkreis => Title::new("Landkreis", "des Landkreises"),
+            Self::Kreis => Title::new("Kreis", "des Kreises"),
+            Self::Other(_) => return None,
+        };
+        Some(title)
+    }
+}
+
+impl KreisTextkennzeichen {
+    /// Returns the title used in official names.
+    pub fn title(&self) -> Title {
+        match self {
+            Self::KreisfreieStadt => Title::new("Kreisfreie Stadt", "der Kreisfreien Stadt"),
+            Self::Stadtkreis => Title::new("Stadtkreis", "des Stadtkreises"),
+            Self::Kreis => Title::new("Kreis", "des Kreises"),
+            Self::Landkreis => Title::new("Landkreis", "des Landkreises"),
+            Self::Regionalverband => Title::new("Regionalverband", "des Regionalverbandes"),
+        }
+    }
+}
+
+impl GemeindeverbandTextkennzeichen {
+    /// Returns the title used in official names.
+    pub fn title(&self) -> Title {
+        match self {
+            Self::VerbandsfreieGemeinde | Self::ErfuellendeGemeinde => {
+                Title::new("Gemeinde", "der Gemeinde")
+            }
+            Self::Amt => Title::new("Amt", "des Amtes"),
+            Self::Samtgemeinde => Title::new("Samtgemeinde", "der Samtgemeinde"),
+            Self::Verbandsgemeinde => Title::new("Verbandsgemeinde", "der Verbandsgemeinde"),
+            Self::Verwaltungsgemeinschaft | Self::VGTraegermodell => {
+                Title::new("Verwaltungsgemeinschaft", "der Verwaltungsgemeinschaft")
+            }
+            Self::Kirchspielslandgemeinde => {
+                Title::new("Kirchspielslandgemeinde", "der Kirchspielslandgemeinde")
+            }
+            Self::Verwaltungsverband => {
+                Title::new("Verwaltungsverband", "des Verwaltungsverbandes")
+            }
+        }
+    }
+}
+
+impl GemeindeTextkennzeichen {
+    /// Returns the title used in official names.
+    pub fn title(&self) -> Title {
+        match self {
+            Self::Markt => Title::new("Markt", "des Marktes"),
+            Self::KreisfreieStadt => Title::new("Kreisfreie Stadt", "der Kreisfreien Stadt"),
+            Self::Stadtkreis => Title::new("Stadtkreis", "des Stadtkreises"),
+            Self::Stadt => Title::new("Stadt", "der Stadt"),
+            Self::KreisangehoerigeGemeinde => Title::new("Gemeinde", "der Gemeinde"),
+            Self::GemeindefreiesGebietBewohnt | Self::GemeindefreiesGebietUnbewohnt => {
+                Title::new("Gemeindefreies Gebiet", "des gemeindefreien Gebiets")
+            }
+            Self::GrosseKreisstadt => Title::new("Große Kreisstadt", "der Großen Kreisstadt"),
+        }
+    }
+}
+
+impl KreisDaten {
+    /// Returns the official name, e.g. `Landkreis Merzig-Wadern`.
+    pub fn official_name(&self) -> OfficialName<'_> {
+        OfficialName::new(&self.name, self.textkennzeichen.title(), Unit::Kreis)
+    }
+}
+
+impl GemeindeverbandDaten {
+    /// Returns the official name, e.g. `Verbandsgemeinde Rhein-Selz`.
+    pub fn official_name(&self) -> OfficialName<'_> {
+        let unit = match self.textkennzeichen {
+            GemeindeverbandTextkennzeichen::VerbandsfreieGemeinde
+            | GemeindeverbandTextkennzeichen::ErfuellendeGemeinde => Unit::EinzelGemeinde,
+            _ => Unit::Verband,
+        };
+        OfficialName::new(&self.name, self.textkennzeichen.title(), unit)
+    }
+}
+
+impl GemeindeDaten {
+    /// Returns the official name, e.g. `Landeshauptstadt Saarbrücken`.
+    pub fn official_name(&self) -> OfficialName<'_> {
+        OfficialName::new(&self.name, self.textkennzeichen.title(), Unit::Gemeinde)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn official_name(name: &str, title: Title, unit: Unit) -> (String, String, String) {
+        let name = Name::parse(name);
+        let official_name = OfficialName::new(&name, title, unit);
+        (
+            official_name.short().to_owned(),
+            official_name.long(),
+            official_name.genitive(),
+        )
+    }
+
+    #[test]
+    fn it_combines_title_and_name() {
+        let (short, long, genitive) = official_name(
+            "Merzig-Wadern",
+            KreisTextkennzeichen::Landkreis.title(),
+            Unit::Kreis,
+        );
+        assert_eq!(short, "Merzig-Wadern");
+        assert_eq!(long, "Landkreis Merzig-Wadern");
+        assert_eq!(genitive, "des Landkreises Merzig-Wadern");
+    }
+
+    #[test]
+    fn it_uses_textkennzeichen_for_kreisfreie_staedte() {
+        let (_, long, genitive) = official_name(
+            "Trier, Stadt",
+            KreisTextkennzeichen::KreisfreieStadt.title(),
+            Unit::Kreis,
+        );
+        assert_eq!(long, "Kreisfreie Stadt Trier");
+        assert_eq!(genitive, "der Kreisfreien Stadt Trier");
+    }
+
+    #[test]
+    fn it_does_not_repeat_title() {
+        let (short, long, _) = official_name(
+            "Regionalverband Saarbrücken",
+            KreisTextkennzeichen::Regionalverband.title(),
+            Unit::Kreis,
+        );
+        assert_eq!(short, "Saarbrücken");
+        assert_eq!(long, "Regionalverband Saarbrücken");
+
+        let (short, long, genitive) = official_name(
+            "Amt Büchen",
+            GemeindeverbandTextkennzeichen::Amt.title(),
+            Unit::Verband,
+        );
+        assert_eq!(short, "Büchen");
+        assert_eq!(long, "Amt Büchen");
+        assert_eq!(genitive, "des Amtes Büchen");
+    }
+
+    #[test]
+    fn it_uses_specific_city_designations() {
+        let (_, long, genitive) = official_name(
+            "Saarbrücken, Landeshauptstadt",
+            GemeindeTextkennzeichen::Stadt.title(),
+            Unit::Gemeinde,
+        );
+        assert_eq!(long, "Landeshauptstadt Saarbrücken");
+        assert_eq!(genitive, "der Landeshauptstadt Saarbrücken");
+
+        let (_, long, _) = official_name(
+            "Friedrichsthal, Stadt",
+            GemeindeTextkennzeichen::Stadt.title(),
+            Unit::Gemeinde,
+        );
+        assert_eq!(long, "Stadt Friedrichsthal");
+
+        let (_, long, _) = official_name(
+            "Beckingen",
+            GemeindeTextkennzeichen::KreisangehoerigeGemeinde.title(),
+            Unit::Gemeinde,
+        );
+        assert_eq!(long, "Gemeinde Beckingen");
+
+        let (short, long, genitive) = official_name(
+            "Stadt Wehlen, Stadt",
+            GemeindeTextkennzeichen::Stadt.title(),
+            Unit::Gemeinde,
+        );
+        assert_eq!(short, "Stadt Wehlen");
+        assert_eq!(long, "Stadt Wehlen");
+        assert_eq!(genitive, "der Stadt Wehlen");
+
+        let (short, long, genitive) = official_name(
+            "Markt Erlbach",
+            GemeindeTextkennzeichen::Markt.title(),
+            Unit::Gemeinde,
+        );
+        assert_eq!(short, "Markt Erlbach");
+        assert_eq!(long, "Markt Erlbach");
+        assert_eq!(genitive, "des Marktes Erlbach");
+    }
+
+    #[test]
+    fn it_uses_designations_of_verbandsfreie_gemeinden() {
+        let title = GemeindeverbandTextkennzeichen::VerbandsfreieGemeinde.title();
+
+        let (_, long, genitive) =
+            official_name("Friedrichsthal, Stadt", title, Unit::EinzelGemeinde);
+        assert_eq!(long, "Stadt Friedrichsthal");
+        assert_eq!(genitive, "der Stadt Friedrichsthal");
+
+        let (_, long, _) =
+            official_name("Saarbrücken, Landeshauptstadt", title, Unit::EinzelGemeinde);
+        assert_eq!(long, "Landeshauptstadt Saarbrücken");
+
+        let (_, long, _) = official_name("Beckingen", title, Unit::EinzelGemeinde);
+        assert_eq!(long, "Gemeinde Beckingen");
+    }
+}
@@ -0,0 +1,394 @@
+//! Compact `u64` encoding of all key types.
+//!
+//! Every key is packed into a single `u64` with the following layout (from
+//! most to least significant bits):
+//!
+//! | Bits    | Field                               |
+//! |---------|-------------------------------------|
+//! | 56 - 63 | Tag (type of key, see [`Pack::TAG`]) |
+//! | 48 - 55 | Land                                |
+//! | 40 - 47 | Regierungsbezirk                    |
+//! | 32 - 39 | Kreis (or Region)                   |
+//! | 16 - 31 | Gemeindeverband                     |
+//! |  0 - 15 | Gemeinde                            |
+//!
+//! Fields that a key doesn't have are zero. Since the tag is stored in the most
+//! significant bits, and the fields are ordered from most to least significant,
+//! the order of packed keys of the same type is the same as the order of the
+//! keys themselves. This means that ranges of keys (e.g. all Gemeinden in a
+//! Kreis) are also ranges of packed keys, which makes the encoding suitable for
+//! integer columns in databases.
+
+use std::{
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+    ops::RangeInclusive,
+};
+
+use crate::{db::IntoRangeKey, error::ParseKeyError};
+
+use super::{
+    gemeinde::{GemeindeSchluessel, RegionalSchluessel},
+    gemeindeverband::GemeindeverbandSchluessel,
+    kreis::KreisSchluessel,
+    land::LandSchluessel,
+    regierungsbezirk::RegierungsbezirkSchluessel,
+    region::RegionSchluessel,
+};
+
+const TAG_SHIFT: u32 = 56;
+const LAND_SHIFT: u32 = 48;
+const REGIERUNGSBEZIRK_SHIFT: u32 = 40;
+const KREIS_SHIFT: u32 = 32;
+const GEMEINDEVERBAND_SHIFT: u32 = 16;
+const GEMEINDE_SHIFT: u32 = 0;
+
+/// Keys that can be packed into a `u64`.
+pub trait Pack: Copy + Ord {
+    /// Tag identifying the type of key. Tags are ordered by level, i.e. a
+    /// Land has a smaller tag than a Kreis.
+    const TAG: u8;
+
+    /// Mask of the bits that are used by this key type (excluding the tag).
+    const MASK: u64;
+
+    /// Packs the key into a `u64`.
+    fn to_u64(self) -> u64;
+
+    /// Unpacks a key from a `u64`.
+    ///
+    /// Returns an error if the tag doesn't match, or bits are set that are not
+    /// used by this key type.
+    fn from_u64(n: u64) -> Result<Self, ParseKeyError>;
+}
+
+fn tag(tag: u8) -> u64 {
+    u64::from(tag) << TAG_SHIFT
+}
+
+fn get_u8(n: u64, shift: u32) -> u8 {
+    (n >> shift) as u8
+}
+
+fn get_u16(n: u64, shift: u32) -> u16 {
+    (n >> shift) as u16
+}
+
+/// Returns the fields of a packed key without its tag.
+fn fields<K: Pack>(key: K) -> u64 {
+    key.to_u64() & K::MASK
+}
+
+/// Unpacks the parent part of the packed key `n`.
+fn parent<K: Pack>(n: u64) -> Result<K, ParseKeyError> {
+    K::from_u64(n & K::MASK | tag(K::TAG))
+}
+
+fn check<K: Pack>(n: u64) -> Result<(), ParseKeyError> {
+    if n & !K::MASK == tag(K::TAG) {
+        Ok(())
+    } else {
+        Err(ParseKeyError::InvalidPacked(n))
+    }
+}
+
+impl Pack for LandSchluessel {
+    const TAG: u8 = 1;
+    const MASK: u64 = 0xff << LAND_SHIFT;
+
+    fn to_u64(self) -> u64 {
+        tag(Self::TAG) | u64::from(self.land) << LAND_SHIFT
+    }
+
+    fn from_u64(n: u64) -> Result<Self, ParseKeyError> {
+        check::<Self>(n)?;
+        Ok(Self::new(get_u8(n, LAND_SHIFT)))
+    }
+}
+
+impl Pack for RegierungsbezirkSchluessel {
+    const TAG: u8 = 2;
+    const MASK: u64 = LandSchluessel::MASK | 0xff << REGIERUNGSBEZIRK_SHIFT;
+
+    fn to_u64(self) -> u64 {
+        fields(self.land)
+            | tag(Self::TAG)
+            | u64::from(self.regierungsbezirk) << REGIERUNGSBEZIRK_SHIFT
+    }
+
+    fn from_u64(n: u64) -> Result<Self, ParseKeyError> {
+        check::<Self>(n)?;
+        Ok(Self::new(
+            parent::<LandSchluessel>(n)?,
+            get_u8(n, REGIERUNGSBEZIRK_SHIFT),
+        ))
+    }
+}
+
+impl Pack for RegionSchluessel {
+    const TAG: u8 = 3;
+    const MASK: u64 = RegierungsbezirkSchluessel::MASK | 0xff << KREIS_SHIFT;
+
+    fn to_u64(self) -> u64 {
+        fields(self.regierungsbezirk) | tag(Self::TAG) | u64::from(self.region) << KREIS_SHIFT
+    }
+
+    fn from_u64(n: u64) -> Result<Self, ParseKeyError> {
+        check::<Self>(n)?;
+        Ok(Self::new(
+            parent::<RegierungsbezirkSchluessel>(n)?,
+            get_u8(n, KREIS_SHIFT),
+        ))
+    }
+}
+
+impl Pack for KreisSchluessel {
+    const TAG: u8 = 4;
+    const MASK: u64 = RegierungsbezirkSchluessel::MASK | 0xff << KREIS_SHIFT;
+
+    fn to_u64(self) -> u64 {
+        fields(self.regierungsbezirk) | tag(Self::TAG) | u64::from(self.kreis) << KREIS_SHIFT
+    }
+
+    fn from_u64(n: u64) -> Result<Self, ParseKeyError> {
+        check::<Self>(n)?;
+        Ok(Self::new(
+            parent::<RegierungsbezirkSchluessel>(n)?,
+            get_u8(n, KREIS_SHIFT),
+        ))
+    }
+}
+
+impl Pack for GemeindeverbandSchluessel {
+    const TAG: u8 = 5;
+    const MASK: u64 = KreisSchluessel::MASK | 0xffff << GEMEINDEVERBAND_SHIFT;
+
+    fn to_u64(self) -> u64 {
+        fields(self.kreis)
+            | tag(Self::TAG)
+            | u64::from(self.gemeindeverband) << GEMEINDEVERBAND_SHIFT
+    }
+
+    fn from_u64(n: u64) -> Result<Self, ParseKeyError> {
+        check::<Self>(n)?;
+        Ok(Self::new(
+            parent::<KreisSchluessel>(n)?,
+            get_u16(n, GEMEINDEVERBAND_SHIFT),
+        ))
+    }
+}
+
+impl Pack for RegionalSchluessel {
+    const TAG: u8 = 6;
+    const MASK: u64 = KreisSchluessel::MASK | 0xffff << GEMEINDE_SHIFT;
+
+    fn to_u64(self) -> u64 {
+        fields(self.kreis) | tag(Self::TAG) | u64::from(self.gemeinde) << GEMEINDE_SHIFT
+    }
+
+    fn from_u64(n: u64) -> Result<Self, ParseKeyError> {
+        check::<Self>(n)?;
+        Ok(Self::new(
+            parent::<KreisSchluessel>(n)?,
+            get_u16(n, GEMEINDE_SHIFT),
+        ))
+    }
+}
+
+impl Pack for GemeindeSchluessel {
+    const TAG: u8 = 7;
+    const MASK: u64 = GemeindeverbandSchluessel::MASK | 0xffff << GEMEINDE_SHIFT;
+
+    fn to_u64(self) -> u64 {
+        fields(self.gemeindeverband) | tag(Self::TAG) | u64::from(self.gemeinde) << GEMEINDE_SHIFT
+    }
+
+    fn from_u64(n: u64) -> Result<Self, ParseKeyError> {
+        check::<Self>(n)?;
+        Ok(Self::new(
+            parent::<GemeindeverbandSchluessel>(n)?,
+            get_u16(n, GEMEINDE_SHIFT),
+        ))
+    }
+}
+
+/// A key packed into a `u64`.
+///
+/// This is ordered exactly like the key `K` it was packed from, and can be used
+/// as target of [`IntoRangeKey`] to get a range of packed keys.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Packed<K> {
+    n: u64,
+    _key: PhantomData<K>,
+}
+
+impl<K: Pack> Packed<K> {
+    pub fn new(key: K) -> Self {
+        Self {
+            n: key.to_u64(),
+            _key: PhantomData,
+        }
+    }
+
+    /// Creates a packed key from a `u64`. Returns an error if `n` is not a
+    /// valid packed key of type `K`.
+    pub fn from_u64(n: u64) -> Result<Self, ParseKeyError> {
+        K::from_u64(n)?;
+        Ok(Self {
+            n,
+            _key: PhantomData,
+        })
+    }
+
+    /// Returns the packed key as `u64`.
+    pub fn to_u64(self) -> u64 {
+        self.n
+    }
+
+    /// Unpacks the key.
+    pub fn unpack(self) -> K {
+        K::from_u64(self.n).expect("Packed always contains a valid key")
+    }
+}
+
+impl<K: Pack> From<K> for Packed<K> {
+    fn from(key: K) -> Self {
+        Self::new(key)
+    }
+}
+
+impl<K> From<Packed<K>> for u64 {
+    fn from(packed: Packed<K>) -> Self {
+        packed.n
+    }
+}
+
+impl<K: Pack> TryFrom<u64> for Packed<K> {
+    type Error = ParseKeyError;
+
+    fn try_from(n: u64) -> Result<Self, Self::Error> {
+        Self::from_u64(n)
+    }
+}
+
+impl<K> Display for Packed<K> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.n)
+    }
+}
+
+/// Creates a range of packed keys from any key that can be turned into a range
+/// of keys.
+impl<P, K> IntoRangeKey<Packed<K>> for P
+where
+    P: IntoRangeKey<K>,
+    K: Pack,
+{
+    fn into_range_key(self) -> RangeInclusive<Packed<K>> {
+        let (start, end) = self.into_range_key().into_inner();
+        Packed::new(start)..=Packed::new(end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gemeinde(s: &str) -> GemeindeSchluessel {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn it_roundtrips_all_key_types() {
+        let gemeinde = gemeinde("100415110511");
+        let gemeindeverband = gemeinde.gemeindeverband;
+        let kreis = gemeindeverband.kreis;
+        let regierungsbezirk = kreis.regierungsbezirk;
+        let land = regierungsbezirk.land;
+        let regional = RegionalSchluessel::from(gemeinde);
+        let region = RegionSchluessel::new(regierungsbezirk, 3);
+
+        assert_eq!(LandSchluessel::from_u64(land.to_u64()).unwrap(), land);
+        assert_eq!(
+            RegierungsbezirkSchluessel::from_u64(regierungsbezirk.to_u64()).unwrap(),
+            regierungsbezirk
+        );
+        assert_eq!(RegionSchluessel::from_u64(region.to_u64()).unwrap(), region);
+        assert_eq!(KreisSchluessel::from_u64(kreis.to_u64()).unwrap(), kreis);
+        assert_eq!(
+            GemeindeverbandSchluessel::from_u64(gemeindeverband.to_u64()).unwrap(),
+            gemeindeverband
+        );
+        assert_eq!(
+            RegionalSchluessel::from_u64(regional.to_u64()).unwrap(),
+            regional
+        );
+        assert_eq!(
+            GemeindeSchluessel::from_u64(gemeinde.to_u64()).unwrap(),
+            gemeinde
+        );
+    }
+
+    #[test]
+    fn it_roundtrips_max_values() {
+        let gemeinde = GemeindeSchluessel::new(
+            GemeindeverbandSchluessel::new(
+                KreisSchluessel::new(
+                    RegierungsbezirkSchluessel::new(LandSchluessel::new(u8::MAX), u8::MAX),
+                    u8::MAX,
+                ),
+                u16::MAX,
+            ),
+            u16::MAX,
+        );
+        assert_eq!(
+            GemeindeSchluessel::from_u64(gemeinde.to_u64()).unwrap(),
+            gemeinde
+        );
+    }
+
+    #[test]
+    fn it_rejects_wrong_tag() {
+        let kreis = crate::kreis!("10041");
+        assert!(GemeindeSchluessel::from_u64(kreis.to_u64()).is_err());
+        assert!(RegionSchluessel::from_u64(kreis.to_u64()).is_err());
+        assert!(Packed::<LandSchluessel>::from_u64(kreis.to_u64()).is_err());
+    }
+
+    #[test]
+    fn it_rejects_unused_bits() {
+        let land = LandSchluessel::new(10);
+        assert!(LandSchluessel::from_u64(land.to_u64() | 1).is_err());
+    }
+
+    #[test]
+    fn it_preserves_order() {
+        let mut keys = vec![
+            gemeinde("100420111111"),
+            gemeinde("100415110511"),
+            gemeinde("100410100100"),
+            gemeinde("091620000000"),
+            gemeinde("100420112112"),
+        ];
+        let mut packed = keys.iter().copied().map(Packed::new).collect::<Vec<_>>();
+
+        keys.sort();
+        packed.sort();
+
+        assert_eq!(
+            packed.into_iter().map(Packed::unpack).collect::<Vec<_>>(),
+            keys
+        );
+    }
+
+    #[test]
+    fn it_creates_packed_ranges() {
+        let kreis = crate::kreis!("10041");
+        let range: RangeInclusive<Packed<GemeindeSchluessel>> = kreis.into_range_key();
+
+        assert!(range.contains(&Packed::new(gemeinde("100415110511"))));
+        assert!(!range.contains(&Packed::new(gemeinde("100420111111"))));
+        assert!(!range.contains(&Packed::new(gemeinde("100400000000"))));
+    }
+}
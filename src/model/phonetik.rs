@@ -0,0 +1,88 @@
+//! Kölner Phonetik, a phonetic algorithm for German words. Words that sound
+//! alike get the same code (e.g. `Friedrichsthal` and `Fridrichstal`).
+//!
+//! See: <https://de.wikipedia.org/wiki/K%C3%B6lner_Phonetik>
+
+use super::slug::transliterate;
+
+/// Returns the Kölner Phonetik code of `s`. Umlauts and `ß` are transliterated
+/// first, and all characters other than letters are ignored.
+pub fn koelner_phonetik(s: &str) -> String {
+    let letters = transliterate(s)
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase())
+        .collect::<Vec<_>>();
+
+    let mut codes = String::with_capacity(letters.len() * 2);
+    for (i, c) in letters.iter().enumerate() {
+        let previous = if i > 0 { Some(letters[i - 1]) } else { None };
+        let next = letters.get(i + 1).copied();
+        let next_in = |set: &str| next.is_some_and(|next| set.contains(next));
+        let previous_in = |set: &str| previous.is_some_and(|previous| set.contains(previous));
+
+        let code = match c {
+            'A' | 'E' | 'I' | 'J' | 'O' | 'U' | 'Y' => "0",
+            'H' => continue,
+            'B' => "1",
+            'P' if next_in("H") => "3",
+            'P' => "1",
+            'D' | 'T' if next_in("CSZ") => "8",
+            'D' | 'T' => "2",
+            'F' | 'V' | 'W' => "3",
+            'G' | 'K' | 'Q' => "4",
+            'C' if previous.is_none() && next_in("AHKLOQRUX") => "4",
+            'C' if previous.is_some() && next_in("AHKOQUX") && !previous_in("SZ") => "4",
+            'C' => "8",
+            'X' if previous_in("CKQ") => "8",
+            'X' => "48",
+            'L' => "5",
+            'M' | 'N' => "6",
+            'R' => "7",
+            'S' | 'Z' => "8",
+            _ => continue,
+        };
+        codes.push_str(code);
+    }
+
+    let mut phonetik = String::with_capacity(codes.len());
+    let mut last = None;
+    for (i, code) in codes.chars().enumerate() {
+        if Some(code) != last && (code != '0' || i == 0) {
+            phonetik.push(code);
+        }
+        last = Some(code);
+    }
+
+    phonetik
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_encodes_words() {
+        assert_eq!(koelner_phonetik("Müller-Lüdenscheidt"), "65752682");
+        assert_eq!(koelner_phonetik("Wikipedia"), "3412");
+        assert_eq!(koelner_phonetik("Breschnew"), "17863");
+        assert_eq!(koelner_phonetik("Xaver"), "4837");
+        assert_eq!(koelner_phonetik(""), "");
+    }
+
+    #[test]
+    fn similar_names_have_the_same_code() {
+        assert_eq!(
+            koelner_phonetik("Friedrichsthal"),
+            koelner_phonetik("Fridrichstal")
+        );
+        assert_eq!(
+            koelner_phonetik("Saarbrücken"),
+            koelner_phonetik("Sarbrücken")
+        );
+        assert_ne!(
+            koelner_phonetik("Saarbrücken"),
+            koelner_phonetik("Saarlouis")
+        );
+    }
+}
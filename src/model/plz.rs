@@ -0,0 +1,148 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use crate::error::Error;
+
+/// A Postleitzahl (PLZ, postcode). This always consists of five digits and may
+/// have leading zeros (e.g. `01067`).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Plz {
+    plz: u32,
+}
+
+impl Plz {
+    /// Largest valid PLZ
+    pub const MAX: u32 = 99999;
+
+    /// Creates a PLZ from its numeric value. Returns `None` if it has more than
+    /// five digits.
+    pub fn new(plz: u32) -> Option<Self> {
+        if plz <= Self::MAX {
+            Some(Self { plz })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the numeric value.
+    pub fn to_u32(&self) -> u32 {
+        self.plz
+    }
+
+    /// Returns the Leitregion, i.e. the first two digits.
+    pub fn leitregion(&self) -> u8 {
+        (self.plz / 1000) as u8
+    }
+
+    /// Returns the Leitzone, i.e. the first digit.
+    pub fn leitzone(&self) -> u8 {
+        (self.plz / 10000) as u8
+    }
+}
+
+impl FromStr for Plz {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 5 || !s.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(Error::InvalidPlz(s.to_owned()));
+        }
+
+        Ok(Self { plz: s.parse()? })
+    }
+}
+
+impl Display for Plz {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:05}", self.plz)
+    }
+}
+
+/// A prefix of a PLZ (e.g. `661`).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PlzPrefix {
+    first: Plz,
+    last: Plz,
+}
+
+impl PlzPrefix {
+    /// Returns the smallest PLZ with this prefix.
+    pub fn first(&self) -> Plz {
+        self.first
+    }
+
+    /// Returns the largest PLZ with this prefix.
+    pub fn last(&self) -> Plz {
+        self.last
+    }
+
+    /// Whether `plz` starts with this prefix.
+    pub fn contains(&self, plz: Plz) -> bool {
+        self.first <= plz && plz <= self.last
+    }
+}
+
+impl FromStr for PlzPrefix {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() > 5 || !s.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(Error::InvalidPlz(s.to_owned()));
+        }
+
+        let scale = 10u32.pow(5 - s.len() as u32);
+        let prefix = if s.is_empty() { 0 } else { s.parse::<u32>()? };
+
+        Ok(Self {
+            first: Plz {
+                plz: prefix * scale,
+            },
+            last: Plz {
+                plz: (prefix + 1) * scale - 1,
+            },
+        })
+    }
+}
+
+impl From<Plz> for PlzPrefix {
+    fn from(plz: Plz) -> Self {
+        Self {
+            first: plz,
+            last: plz,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_keeps_leading_zeros() {
+        let plz = "01067".parse::<Plz>().unwrap();
+        assert_eq!(plz.to_u32(), 1067);
+        assert_eq!(plz.to_string(), "01067");
+        assert_eq!(plz.leitregion(), 1);
+        assert_eq!(plz.leitzone(), 0);
+    }
+
+    #[test]
+    fn it_rejects_invalid_plz() {
+        assert!("6611".parse::<Plz>().is_err());
+        assert!("661111".parse::<Plz>().is_err());
+        assert!("6611a".parse::<Plz>().is_err());
+        assert!("+6611".parse::<Plz>().is_err());
+        assert!(Plz::new(100000).is_none());
+    }
+
+    #[test]
+    fn it_parses_prefixes() {
+        let prefix = "661".parse::<PlzPrefix>().unwrap();
+        assert_eq!(prefix.first().to_string(), "66100");
+        assert_eq!(prefix.last().to_string(), "66199");
+        assert!(prefix.contains("66111".parse().unwrap()));
+        assert!(!prefix.contains("66200".parse().unwrap()));
+    }
+}
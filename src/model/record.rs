@@ -0,0 +1,362 @@
+//! Common interface of the records of all levels, for code that treats all
+//! levels uniformly (e.g. exporters).
+
+use std::fmt::{self, Display, Formatter};
+
+use chrono::NaiveDate;
+
+use super::{
+    datensatz::{Datensatz, DatensatzRef, Schluessel},
+    gemeinde::{GemeindeDaten, GemeindeSchluessel, GemeindeTextkennzeichen},
+    gemeindeverband::{
+        GemeindeverbandDaten,
+        GemeindeverbandSchluessel,
+        GemeindeverbandTextkennzeichen,
+    },
+    kreis::{KreisDaten, KreisSchluessel, KreisTextkennzeichen},
+    land::{LandDaten, LandSchluessel},
+    name::Name,
+    regierungsbezirk::{RegierungsbezirkDaten, RegierungsbezirkSchluessel},
+    region::{RegionDaten, RegionSchluessel},
+};
+
+/// Level of a unit in the hierarchy, from top to bottom.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Ebene {
+    Land,
+    Regierungsbezirk,
+    Region,
+    Kreis,
+    Gemeindeverband,
+    Gemeinde,
+}
+
+impl Ebene {
+    /// Returns the German label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Land => "Land",
+            Self::Regierungsbezirk => "Regierungsbezirk",
+            Self::Region => "Region",
+            Self::Kreis => "Kreis",
+            Self::Gemeindeverband => "Gemeindeverband",
+            Self::Gemeinde => "Gemeinde",
+        }
+    }
+
+    /// Returns an English label.
+    pub fn label_en(&self) -> &'static str {
+        match self {
+            Self::Land => "State",
+            Self::Regierungsbezirk => "Government district",
+            Self::Region => "Region",
+            Self::Kreis => "District",
+            Self::Gemeindeverband => "Municipal association",
+            Self::Gemeinde => "Municipality",
+        }
+    }
+}
+
+impl Display for Ebene {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+impl Schluessel {
+    /// Returns the level of the unit.
+    pub fn ebene(&self) -> Ebene {
+        match self {
+            Self::Land(_) => Ebene::Land,
+            Self::Regierungsbezirk(_) => Ebene::Regierungsbezirk,
+            Self::Region(_) => Ebene::Region,
+            Self::Kreis(_) => Ebene::Kreis,
+            Self::Gemeindeverband(_) => Ebene::Gemeindeverband,
+            Self::Gemeinde(_) => Ebene::Gemeinde,
+        }
+    }
+}
+
+/// Type of a unit of any level. Only Kreise, Gemeindeverbaende and Gemeinden
+/// have a Textkennzeichen.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Textkennzeichen {
+    Kreis(KreisTextkennzeichen),
+    Gemeindeverband(GemeindeverbandTextkennzeichen),
+    Gemeinde(GemeindeTextkennzeichen),
+}
+
+impl Textkennzeichen {
+    /// Returns the German label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Kreis(textkennzeichen) => textkennzeichen.label(),
+            Self::Gemeindeverband(textkennzeichen) => textkennzeichen.label(),
+            Self::Gemeinde(textkennzeichen) => textkennzeichen.label(),
+        }
+    }
+
+    /// Returns an English label.
+    pub fn label_en(&self) -> &'static str {
+        match self {
+            Self::Kreis(textkennzeichen) => textkennzeichen.label_en(),
+            Self::Gemeindeverband(textkennzeichen) => textkennzeichen.label_en(),
+            Self::Gemeinde(textkennzeichen) => textkennzeichen.label_en(),
+        }
+    }
+
+    /// Whether the unit is a city. Gemeindeverbaende are never cities.
+    pub fn is_city(&self) -> bool {
+        match self {
+            Self::Kreis(textkennzeichen) => textkennzeichen.is_city(),
+            Self::Gemeindeverband(_) => false,
+            Self::Gemeinde(textkennzeichen) => textkennzeichen.is_city(),
+        }
+    }
+}
+
+impl From<KreisTextkennzeichen> for Textkennzeichen {
+    fn from(textkennzeichen: KreisTextkennzeichen) -> Self {
+        Self::Kreis(textkennzeichen)
+    }
+}
+
+impl From<GemeindeverbandTextkennzeichen> for Textkennzeichen {
+    fn from(textkennzeichen: GemeindeverbandTextkennzeichen) -> Self {
+        Self::Gemeindeverband(textkennzeichen)
+    }
+}
+
+impl From<GemeindeTextkennzeichen> for Textkennzeichen {
+    fn from(textkennzeichen: GemeindeTextkennzeichen) -> Self {
+        Self::Gemeinde(textkennzeichen)
+    }
+}
+
+impl Display for Textkennzeichen {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// Calls `$e` with `$record` bound to the record in a [`DatensatzRef`].
+macro_rules! dispatch {
+    ($datensatz:expr, $record:ident => $e:expr) => {
+        match $datensatz {
+            DatensatzRef::Land($record) => $e,
+            DatensatzRef::Regierungsbezirk($record) => $e,
+            DatensatzRef::Region($record) => $e,
+            DatensatzRef::Kreis($record) => $e,
+            DatensatzRef::Gemeindeverband($record) => $e,
+            DatensatzRef::Gemeinde($record) => $e,
+        }
+    };
+}
+
+/// Common interface of the records of all levels.
+pub trait Record {
+    /// Key type of the record. For [`Datensatz`] and [`DatensatzRef`] this is
+    /// [`Schluessel`], which covers all levels.
+    type Schluessel: Copy + Into<Schluessel>;
+
+    /// Returns the key of the unit.
+    fn schluessel(&self) -> Self::Schluessel;
+
+    /// Returns the level of the unit.
+    fn ebene(&self) -> Ebene {
+        self.schluessel().into().ebene()
+    }
+
+    /// Returns the name of the unit.
+    fn name(&self) -> &Name;
+
+    /// Returns the Gebietsstand (i.e. timestamp) of the record.
+    fn gebietsstand(&self) -> &NaiveDate;
+
+    /// Returns the location of the administration. For Laender this is the
+    /// seat of the government. Gemeinden don't have one.
+    fn sitz_verwaltung(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the type of the unit, if the level has one.
+    fn textkennzeichen(&self) -> Option<Textkennzeichen> {
+        None
+    }
+}
+
+impl Record for LandDaten {
+    type Schluessel = LandSchluessel;
+
+    fn schluessel(&self) -> Self::Schluessel {
+        self.schluessel
+    }
+
+    fn name(&self) -> &Name {
+        &self.name
+    }
+
+    fn gebietsstand(&self) -> &NaiveDate {
+        &self.gebietsstand
+    }
+
+    fn sitz_verwaltung(&self) -> Option<&str> {
+        Some(&self.sitz_regierung)
+    }
+}
+
+impl Record for RegierungsbezirkDaten {
+    type Schluessel = RegierungsbezirkSchluessel;
+
+    fn schluessel(&self) -> Self::Schluessel {
+        self.schluessel
+    }
+
+    fn name(&self) -> &Name {
+        &self.name
+    }
+
+    fn gebietsstand(&self) -> &NaiveDate {
+        &self.gebietsstand
+    }
+
+    fn sitz_verwaltung(&self) -> Option<&str> {
+        Some(&self.sitz_verwaltung)
+    }
+}
+
+impl Record for RegionDaten {
+    type Schluessel = RegionSchluessel;
+
+    fn schluessel(&self) -> Self::Schluessel {
+        self.schluessel
+    }
+
+    fn name(&self) -> &Name {
+        &self.name
+    }
+
+    fn gebietsstand(&self) -> &NaiveDate {
+        &self.gebietsstand
+    }
+
+    fn sitz_verwaltung(&self) -> Option<&str> {
+        Some(&self.sitz_verwaltung)
+    }
+}
+
+impl Record for KreisDaten {
+    type Schluessel = KreisSchluessel;
+
+    fn schluessel(&self) -> Self::Schluessel {
+        self.schluessel
+    }
+
+    fn name(&self) -> &Name {
+        &self.name
+    }
+
+    fn gebietsstand(&self) -> &NaiveDate {
+        &self.gebietsstand
+    }
+
+    fn sitz_verwaltung(&self) -> Option<&str> {
+        Some(&self.sitz_verwaltung)
+    }
+
+    fn textkennzeichen(&self) -> Option<Textkennzeichen> {
+        Some(self.textkennzeichen.into())
+    }
+}
+
+impl Record for GemeindeverbandDaten {
+    type Schluessel = GemeindeverbandSchluessel;
+
+    fn schluessel(&self) -> Self::Schluessel {
+        self.schluessel
+    }
+
+    fn name(&self) -> &Name {
+        &self.name
+    }
+
+    fn gebietsstand(&self) -> &NaiveDate {
+        &self.gebietsstand
+    }
+
+    fn sitz_verwaltung(&self) -> Option<&str> {
+        self.sitz_verwaltung.as_deref()
+    }
+
+    fn textkennzeichen(&self) -> Option<Textkennzeichen> {
+        Some(self.textkennzeichen.into())
+    }
+}
+
+impl Record for GemeindeDaten {
+    type Schluessel = GemeindeSchluessel;
+
+    fn schluessel(&self) -> Self::Schluessel {
+        self.schluessel
+    }
+
+    fn name(&self) -> &Name {
+        &self.name
+    }
+
+    fn gebietsstand(&self) -> &NaiveDate {
+        &self.gebietsstand
+    }
+
+    fn textkennzeichen(&self) -> Option<Textkennzeichen> {
+        Some(self.textkennzeichen.into())
+    }
+}
+
+impl<'a> Record for DatensatzRef<'a> {
+    type Schluessel = Schluessel;
+
+    fn schluessel(&self) -> Schluessel {
+        dispatch!(*self, record => record.schluessel().into())
+    }
+
+    fn name(&self) -> &Name {
+        dispatch!(*self, record => record.name())
+    }
+
+    fn gebietsstand(&self) -> &NaiveDate {
+        dispatch!(*self, record => record.gebietsstand())
+    }
+
+    fn sitz_verwaltung(&self) -> Option<&str> {
+        dispatch!(*self, record => record.sitz_verwaltung())
+    }
+
+    fn textkennzeichen(&self) -> Option<Textkennzeichen> {
+        dispatch!(*self, record => record.textkennzeichen())
+    }
+}
+
+impl Record for Datensatz {
+    type Schluessel = Schluessel;
+
+    fn schluessel(&self) -> Schluessel {
+        DatensatzRef::from(self).schluessel()
+    }
+
+    fn name(&self) -> &Name {
+        dispatch!(DatensatzRef::from(self), record => record.name())
+    }
+
+    fn gebietsstand(&self) -> &NaiveDate {
+        dispatch!(DatensatzRef::from(self), record => record.gebietsstand())
+    }
+
+    fn sitz_verwaltung(&self) -> Option<&str> {
+        dispatch!(DatensatzRef::from(self), record => record.sitz_verwaltung())
+    }
+
+    fn textkennzeichen(&self) -> Option<Textkennzeichen> {
+        DatensatzRef::from(self).textkennzeichen()
+    }
+}
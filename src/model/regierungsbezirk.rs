@@ -5,9 +5,12 @@ use std::{
 
 use chrono::NaiveDate;
 
-use crate::error::ParseKeyError;
+use crate::{
+    error::ParseKeyError,
+    macros::{check_literal_length, literal_digits},
+};
 
-use super::land::LandSchluessel;
+use super::{land::LandSchluessel, name::Name};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct RegierungsbezirkSchluessel {
@@ -16,12 +19,33 @@ pub struct RegierungsbezirkSchluessel {
 }
 
 impl RegierungsbezirkSchluessel {
-    pub fn new(land: LandSchluessel, regierungsbezirk: u8) -> Self {
+    pub const fn new(land: LandSchluessel, regierungsbezirk: u8) -> Self {
         Self {
             land,
             regierungsbezirk,
         }
     }
+
+    /// Creates a key from a literal (e.g. `"100"`). This is used by the
+    /// [`regierungsbezirk!`](crate::regierungsbezirk) macro.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not a valid key. In const context this is a compile
+    /// error.
+    pub const fn from_literal(s: &str) -> Self {
+        check_literal_length(s, 3);
+        Self::from_literal_digits(s)
+    }
+
+    /// Creates a key from the first 3 digits of `s`, without checking the
+    /// length.
+    pub(crate) const fn from_literal_digits(s: &str) -> Self {
+        Self::new(
+            LandSchluessel::from_literal_digits(s),
+            literal_digits(s, 2, 3) as u8,
+        )
+    }
 }
 
 impl FromStr for RegierungsbezirkSchluessel {
@@ -41,7 +65,7 @@ impl FromStr for RegierungsbezirkSchluessel {
 
 impl Display for RegierungsbezirkSchluessel {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}{:1}", self.land, self.regierungsbezirk)
+        write!(f, "{}{:01}", self.land, self.regierungsbezirk)
     }
 }
 
@@ -61,7 +85,7 @@ pub struct RegierungsbezirkDaten {
     pub schluessel: RegierungsbezirkSchluessel,
 
     /// Name of Regierunsbezirk
-    pub name: String,
+    pub name: Name,
 
     /// Location of administration
     pub sitz_verwaltung: String,
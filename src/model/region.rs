@@ -5,9 +5,17 @@ use std::{
 
 use chrono::NaiveDate;
 
-use crate::error::ParseKeyError;
+use crate::{
+    error::ParseKeyError,
+    macros::{check_literal_length, literal_digits},
+};
 
-use super::{land::LandSchluessel, regierungsbezirk::RegierungsbezirkSchluessel};
+use super::{
+    kreis::KreisSchluessel,
+    land::LandSchluessel,
+    name::Name,
+    regierungsbezirk::RegierungsbezirkSchluessel,
+};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct RegionSchluessel {
@@ -16,12 +24,63 @@ pub struct RegionSchluessel {
 }
 
 impl RegionSchluessel {
-    pub fn new(regierungsbezirk: RegierungsbezirkSchluessel, region: u8) -> Self {
+    pub const fn new(regierungsbezirk: RegierungsbezirkSchluessel, region: u8) -> Self {
         Self {
             regierungsbezirk,
             region,
         }
     }
+
+    /// Returns the key of the Region a Kreis belongs to. In Baden-Wuerttemberg,
+    /// the first digit of the Kreis number is the number of the Region in the
+    /// Regierungsbezirk (e.g. Kreis `08115` is in Region `0811`). Other Laender
+    /// don't have Regionen, so the result is meaningless there.
+    pub const fn of_kreis(kreis: KreisSchluessel) -> Self {
+        Self::new(kreis.regierungsbezirk, kreis.kreis / 10)
+    }
+
+    /// Whether the Kreis belongs to this Region.
+    pub fn contains(&self, kreis: KreisSchluessel) -> bool {
+        Self::of_kreis(kreis) == *self
+    }
+
+    /// Returns the first Kreis number that can belong to this Region. Kreis
+    /// numbers only have two digits, so Regionen of 10 or more can't have any
+    /// Kreise. Their range lies beyond all Kreise, and saturates for Regionen
+    /// above 25.
+    pub(crate) const fn first_kreis(&self) -> KreisSchluessel {
+        KreisSchluessel::new(self.regierungsbezirk, self.region.saturating_mul(10))
+    }
+
+    /// Returns the last Kreis number that can belong to this Region. This
+    /// saturates like [`first_kreis`](Self::first_kreis).
+    pub(crate) const fn last_kreis(&self) -> KreisSchluessel {
+        KreisSchluessel::new(
+            self.regierungsbezirk,
+            self.region.saturating_mul(10).saturating_add(9),
+        )
+    }
+
+    /// Creates a key from a literal (e.g. `"0811"`). This is used by the
+    /// [`region!`](crate::region) macro.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not a valid key. In const context this is a compile
+    /// error.
+    pub const fn from_literal(s: &str) -> Self {
+        check_literal_length(s, 4);
+        Self::from_literal_digits(s)
+    }
+
+    /// Creates a key from the first 4 digits of `s`, without checking the
+    /// length.
+    pub(crate) const fn from_literal_digits(s: &str) -> Self {
+        Self::new(
+            RegierungsbezirkSchluessel::from_literal_digits(s),
+            literal_digits(s, 3, 4) as u8,
+        )
+    }
 }
 
 impl FromStr for RegionSchluessel {
@@ -41,7 +100,7 @@ impl FromStr for RegionSchluessel {
 
 impl Display for RegionSchluessel {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}{:1}", self.regierungsbezirk, self.region)
+        write!(f, "{}{:01}", self.regierungsbezirk, self.region)
     }
 }
 
@@ -67,7 +126,7 @@ pub struct RegionDaten {
     pub schluessel: RegionSchluessel,
 
     /// Name of Region
-    pub name: String,
+    pub name: Name,
 
     /// Location of administration
     pub sitz_verwaltung: String,
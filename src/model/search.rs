@@ -0,0 +1,279 @@
+//! Searching units by name. Names are folded before they're compared, so that
+//! the search is case-insensitive, umlauts match their transliteration (e.g.
+//! `Saarbrücken` and `Saarbruecken`), and punctuation and common abbreviations
+//! don't matter (e.g. `St. Ingbert` and `Sankt Ingbert`).
+//!
+//! For messy input, the fuzzy search scores names by their trigram similarity,
+//! and boosts names that sound alike according to the Kölner Phonetik.
+
+use super::{
+    datensatz::{DatensatzRef, Schluessel},
+    name::Name,
+    phonetik::koelner_phonetik,
+    record::Ebene,
+    slug::transliterate,
+};
+
+/// Abbreviations in names, that are expanded when folding.
+const ABBREVIATIONS: &[(&str, &str)] = &[("st", "sankt")];
+
+/// Folds `s` for comparison: Lowercases, transliterates umlauts and `ß`,
+/// replaces punctuation with single spaces, and expands abbreviations (e.g.
+/// `St. Ingbert` to `sankt ingbert`).
+pub fn fold(s: &str) -> String {
+    let transliterated = transliterate(s);
+    let words = transliterated
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            ABBREVIATIONS
+                .iter()
+                .find(|(abbreviation, _)| *abbreviation == word)
+                .map_or(word, |(_, expanded)| expanded)
+        })
+        .collect::<Vec<_>>();
+    words.join(" ")
+}
+
+/// Returns the folded forms of a name under which a unit can be found: the
+/// full name, the name without designation and the name in a minority
+/// language.
+pub(crate) fn name_forms(name: &Name) -> Vec<String> {
+    let mut forms = vec![fold(&name.full), fold(&name.base)];
+    if let Some(minority) = &name.minority {
+        forms.push(fold(&minority.name));
+    }
+    forms.retain(|form| !form.is_empty());
+    forms.sort();
+    forms.dedup();
+    forms
+}
+
+/// Returns the Kölner Phonetik codes of the name forms (see
+/// [`name_forms`]).
+pub(crate) fn phonetic_forms(name: &Name) -> Vec<String> {
+    let mut forms = name_forms(name)
+        .iter()
+        .map(|form| koelner_phonetik(form))
+        .filter(|code| !code.is_empty())
+        .collect::<Vec<_>>();
+    forms.sort();
+    forms.dedup();
+    forms
+}
+
+/// Returns the distinct trigrams of an already folded string. Like in
+/// PostgreSQL's `pg_trgm`, the string is padded with two spaces at the start
+/// and one at the end, so that the first letters carry more weight.
+pub(crate) fn trigrams(folded: &str) -> Vec<String> {
+    let padded = format!("  {} ", folded).chars().collect::<Vec<_>>();
+    let mut trigrams = padded
+        .windows(3)
+        .map(|window| window.iter().collect::<String>())
+        .collect::<Vec<_>>();
+    trigrams.sort();
+    trigrams.dedup();
+    trigrams
+}
+
+/// Returns the trigrams of all name forms (see [`name_forms`]).
+pub(crate) fn name_trigrams(name: &Name) -> Vec<String> {
+    let mut trigrams = name_forms(name)
+        .iter()
+        .flat_map(|form| trigrams(form))
+        .collect::<Vec<_>>();
+    trigrams.sort();
+    trigrams.dedup();
+    trigrams
+}
+
+/// Returns the similarity of two strings between `0.0` and `1.0`, as the Dice
+/// coefficient of their trigrams. The strings are folded first.
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a = trigrams(&fold(a));
+    let b = trigrams(&fold(b));
+    let shared = a
+        .iter()
+        .filter(|trigram| b.binary_search(trigram).is_ok())
+        .count();
+    2.0 * shared as f64 / (a.len() + b.len()) as f64
+}
+
+/// A name pattern with `*` as wildcard (e.g. `Neustadt*`). Names and pattern
+/// are folded before they're compared (see [`fold`]).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct NamePattern {
+    parts: Vec<String>,
+}
+
+impl NamePattern {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            parts: pattern.split('*').map(fold).collect(),
+        }
+    }
+
+    /// Whether the full name, the name without designation or the name in a
+    /// minority language matches the pattern.
+    pub fn matches(&self, name: &Name) -> bool {
+        name_forms(name)
+            .iter()
+            .any(|form| self.matches_folded(form))
+    }
+
+    fn matches_folded(&self, s: &str) -> bool {
+        let (first, rest) = match self.parts.split_first() {
+            Some((first, rest)) if !rest.is_empty() => (first, rest),
+            _ => return self.parts.first().is_some_and(|part| part == s),
+        };
+        let (last, middle) = rest.split_last().expect("rest is not empty");
+
+        let mut s = match s.strip_prefix(first.as_str()) {
+            Some(s) => s,
+            None => return false,
+        };
+        for part in middle {
+            match s.find(part.as_str()) {
+                Some(i) => s = &s[i + part.len()..],
+                None => return false,
+            }
+        }
+        s.ends_with(last.as_str())
+    }
+}
+
+/// Options for [`Database::search_with`](crate::Database::search_with).
+#[derive(Clone, Debug, Default)]
+pub struct SearchOptions {
+    /// Also return units whose name starts with the query.
+    pub prefix: bool,
+
+    /// Only return units of this level.
+    pub ebene: Option<Ebene>,
+
+    /// Only return units that lie within this unit (e.g. a Land).
+    pub within: Option<Schluessel>,
+
+    /// Maximum number of results.
+    pub limit: Option<usize>,
+}
+
+/// How a name matched the query. Exact matches rank before prefix matches.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum MatchKind {
+    /// The name is equal to the query.
+    Exact,
+
+    /// The name starts with the query.
+    Prefix,
+}
+
+/// A unit found by name.
+#[derive(Copy, Clone, Debug)]
+pub struct SearchResult<'a> {
+    /// The record of the unit
+    pub datensatz: DatensatzRef<'a>,
+
+    /// How the name matched the query
+    pub match_kind: MatchKind,
+
+    /// Population of the unit, which is used to rank results of the same
+    /// [`MatchKind`].
+    pub population: u64,
+}
+
+/// Options for [`Database::fuzzy_search`](crate::Database::fuzzy_search).
+#[derive(Clone, Debug)]
+pub struct FuzzyOptions {
+    /// Minimum score of a result, between `0.0` and `1.0`. Defaults to `0.5`.
+    pub min_score: f64,
+
+    /// Boost names that sound alike according to the Kölner Phonetik. A
+    /// phonetic match moves the score halfway towards `1.0`. Defaults to
+    /// `true`.
+    pub phonetik: bool,
+
+    /// Only return units of this level.
+    pub ebene: Option<Ebene>,
+
+    /// Only return units that lie within this unit (e.g. a Land).
+    pub within: Option<Schluessel>,
+
+    /// Maximum number of results.
+    pub limit: Option<usize>,
+}
+
+impl Default for FuzzyOptions {
+    fn default() -> Self {
+        Self {
+            min_score: 0.5,
+            phonetik: true,
+            ebene: None,
+            within: None,
+            limit: None,
+        }
+    }
+}
+
+/// A unit found by fuzzy search.
+#[derive(Clone, Debug)]
+pub struct FuzzyMatch<'a> {
+    /// The record of the unit
+    pub datensatz: DatensatzRef<'a>,
+
+    /// Score between `0.0` and `1.0`. `1.0` is an exact match of the folded
+    /// names.
+    pub score: f64,
+
+    /// Whether the name sounds like the query, according to the Kölner
+    /// Phonetik.
+    pub phonetic: bool,
+
+    /// The unit and its parents, from the Land down to the unit (see
+    /// [`Database::hierarchy`](crate::Database::hierarchy)).
+    pub path: Vec<DatensatzRef<'a>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_folds_names() {
+        assert_eq!(fold("Saarbrücken"), "saarbruecken");
+        assert_eq!(fold("SAARBRUECKEN"), "saarbruecken");
+        assert_eq!(fold("St. Ingbert"), "sankt ingbert");
+        assert_eq!(fold("Sankt  Ingbert"), "sankt ingbert");
+        assert_eq!(fold("Weißenburg i.Bay."), "weissenburg i bay");
+        assert_eq!(fold("Halle (Saale)"), "halle saale");
+    }
+
+    #[test]
+    fn it_returns_name_forms() {
+        assert_eq!(
+            name_forms(&Name::parse("Saarbrücken, Landeshauptstadt")),
+            vec!["saarbruecken", "saarbruecken landeshauptstadt"]
+        );
+        assert_eq!(name_forms(&Name::parse("Beckingen")), vec!["beckingen"]);
+    }
+
+    #[test]
+    fn it_scores_similarity() {
+        assert_eq!(similarity("Saarbrücken", "saarbruecken"), 1.0);
+        assert!(similarity("Losheim a. See", "Losheim am See") > 0.8);
+        assert!(similarity("Fridrichstal", "Friedrichsthal") > 0.5);
+        assert!(similarity("Beckingen", "Friedrichsthal") < 0.1);
+    }
+
+    #[test]
+    fn it_matches_name_patterns() {
+        let name = Name::parse("Neustadt a.d. Aisch, Stadt");
+        assert!(NamePattern::new("Neustadt*").matches(&name));
+        assert!(NamePattern::new("*aisch").matches(&name));
+        assert!(NamePattern::new("neu*a.d.*").matches(&name));
+        assert!(NamePattern::new("Neustadt a. d. Aisch").matches(&name));
+        assert!(NamePattern::new("*").matches(&name));
+        assert!(!NamePattern::new("Neustadt").matches(&name));
+        assert!(!NamePattern::new("*Saale*").matches(&name));
+    }
+}
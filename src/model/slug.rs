@@ -0,0 +1,214 @@
+//! URL slugs for units, e.g. `regionalverband-saarbruecken`, and slug paths
+//! along the hierarchy, e.g. `saarland/regionalverband-saarbruecken/
+//! friedrichsthal`.
+//!
+//! Slugs are unique among siblings. Units whose names collide (e.g. the many
+//! Gemeinden named `Neustadt`) get their key appended to the slug. All
+//! colliding units are suffixed, so that a slug doesn't depend on which unit
+//! happens to be inserted first.
+
+use std::collections::HashMap;
+
+use super::{
+    datensatz::Schluessel,
+    gemeinde::RegionalSchluessel,
+    kreis::KreisSchluessel,
+    land::LandSchluessel,
+    name::Name,
+};
+
+/// Lowercases `s` and transliterates umlauts, `ß` and other letters with
+/// diacritics to ASCII (e.g. `Saarbrücken` to `saarbruecken`, `Chóśebuz` to
+/// `chosebuz`). Other characters are kept.
+pub fn transliterate(s: &str) -> String {
+    let mut transliterated = String::with_capacity(s.len());
+
+    for c in s.chars().flat_map(char::to_lowercase) {
+        let replacement = match c {
+            'ä' | 'æ' => "ae",
+            'ö' | 'ø' => "oe",
+            'ü' => "ue",
+            'ß' => "ss",
+            'å' => "aa",
+            'á' | 'à' | 'â' => "a",
+            'é' | 'è' | 'ê' | 'ë' | 'ě' => "e",
+            'í' | 'ì' | 'î' | 'ï' => "i",
+            'ó' | 'ò' | 'ô' => "o",
+            'ú' | 'ù' | 'û' => "u",
+            'ý' => "y",
+            'ç' | 'ć' | 'č' => "c",
+            'ł' => "l",
+            'ń' | 'ň' => "n",
+            'ŕ' | 'ř' => "r",
+            'ś' | 'š' => "s",
+            'ź' | 'ż' | 'ž' => "z",
+            _ => {
+                transliterated.push(c);
+                continue;
+            }
+        };
+        transliterated.push_str(replacement);
+    }
+
+    transliterated
+}
+
+/// Creates a slug from `s`, e.g. `Losheim am See` to `losheim-am-see`. The slug
+/// only contains lowercase ASCII letters, digits and single dashes.
+pub fn slugify(s: &str) -> String {
+    let mut slug = String::with_capacity(s.len());
+
+    for c in transliterate(s).chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    if slug.ends_with('-') {
+        slug.pop();
+    }
+
+    slug
+}
+
+/// Creates the slug for a name. Designations are stripped (e.g.
+/// `Saarbrücken, Landeshauptstadt` to `saarbruecken`), except for those that
+/// tell a Kreis apart from a kreisfreie Stadt of the same name (see
+/// [`Name::short`]).
+pub fn name_slug(name: &Name) -> String {
+    slugify(&name.short())
+}
+
+/// Key that is appended to the slug when the names of siblings collide. For
+/// Gemeinden this is the AGS.
+pub(crate) fn disambiguator(schluessel: Schluessel) -> String {
+    match schluessel {
+        Schluessel::Gemeinde(schluessel) => RegionalSchluessel::from(schluessel).to_string(),
+        _ => schluessel.to_string(),
+    }
+}
+
+/// Slugs of all units, and slug paths of Laender, Kreise and Gemeinden.
+/// Created by [`Database::slugs`](crate::Database::slugs).
+#[derive(Clone, Debug, Default)]
+pub struct Slugs {
+    slugs: HashMap<Schluessel, String>,
+    paths: HashMap<Schluessel, String>,
+    lookup: HashMap<String, Schluessel>,
+}
+
+impl Slugs {
+    /// Returns the slug of the unit with key `k`.
+    pub fn slug<K: Into<Schluessel>>(&self, k: K) -> Option<&str> {
+        self.slugs.get(&k.into()).map(String::as_str)
+    }
+
+    /// Returns the slug path of the unit with key `k`, e.g.
+    /// `saarland/regionalverband-saarbruecken/friedrichsthal`. Only Laender,
+    /// Kreise and Gemeinden have paths.
+    pub fn path<K: Into<Schluessel>>(&self, k: K) -> Option<&str> {
+        self.paths.get(&k.into()).map(String::as_str)
+    }
+
+    /// Returns the key of the unit with slug path `path`. Leading and trailing
+    /// slashes are ignored, and the lookup is case-insensitive.
+    pub fn lookup(&self, path: &str) -> Option<Schluessel> {
+        self.lookup
+            .get(&path.trim_matches('/').to_lowercase())
+            .copied()
+    }
+
+    /// Assigns slugs to a group of siblings. Colliding slugs are suffixed with
+    /// the key.
+    pub(crate) fn insert_siblings<'a, I>(&mut self, siblings: I)
+    where
+        I: IntoIterator<Item = (Schluessel, &'a Name)>,
+    {
+        let siblings = siblings
+            .into_iter()
+            .map(|(schluessel, name)| (schluessel, name_slug(name)))
+            .collect::<Vec<_>>();
+
+        let mut counts = HashMap::<&str, usize>::new();
+        for (_, slug) in &siblings {
+            *counts.entry(slug).or_default() += 1;
+        }
+
+        let slugs = siblings
+            .iter()
+            .map(|(schluessel, slug)| {
+                let slug = if counts[slug.as_str()] > 1 || slug.is_empty() {
+                    slugify(&format!("{} {}", slug, disambiguator(*schluessel)))
+                } else {
+                    slug.clone()
+                };
+                (*schluessel, slug)
+            })
+            .collect::<Vec<_>>();
+
+        self.slugs.extend(slugs);
+    }
+
+    /// Creates the slug paths of Laender, Kreise and Gemeinden. The slugs must
+    /// have been inserted before.
+    pub(crate) fn insert_paths<I>(&mut self, keys: I)
+    where
+        I: IntoIterator<Item = Schluessel>,
+    {
+        for schluessel in keys {
+            let ancestors = match schluessel {
+                Schluessel::Land(_) => vec![schluessel],
+                Schluessel::Kreis(kreis) => vec![LandSchluessel::from(kreis).into(), schluessel],
+                Schluessel::Gemeinde(gemeinde) => {
+                    vec![
+                        LandSchluessel::from(gemeinde).into(),
+                        KreisSchluessel::from(gemeinde).into(),
+                        schluessel,
+                    ]
+                }
+                _ => continue,
+            };
+
+            let segments = ancestors
+                .iter()
+                .map(|k| self.slugs.get(k).map(String::as_str))
+                .collect::<Option<Vec<_>>>();
+
+            if let Some(segments) = segments {
+                let path = segments.join("/");
+                self.lookup.insert(path.clone(), schluessel);
+                self.paths.insert(schluessel, path);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_creates_slugs() {
+        assert_eq!(slugify("Saarbrücken"), "saarbruecken");
+        assert_eq!(slugify("Losheim am See"), "losheim-am-see");
+        assert_eq!(slugify("Weißenburg i. Bay."), "weissenburg-i-bay");
+        assert_eq!(slugify("Neustadt (Wied)"), "neustadt-wied");
+        assert_eq!(slugify("Chóśebuz"), "chosebuz");
+        assert_eq!(slugify("  St. Ingbert "), "st-ingbert");
+    }
+
+    #[test]
+    fn it_strips_designations() {
+        assert_eq!(
+            name_slug(&Name::parse("Saarbrücken, Landeshauptstadt")),
+            "saarbruecken"
+        );
+        assert_eq!(
+            name_slug(&Name::parse("Regionalverband Saarbrücken")),
+            "regionalverband-saarbruecken"
+        );
+        assert_eq!(name_slug(&Name::parse("Reg.-Bez. Trier")), "trier");
+    }
+}
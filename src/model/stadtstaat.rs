@@ -0,0 +1,107 @@
+//! City-states (Stadtstaaten). In Berlin and Hamburg the Land, Kreis,
+//! Gemeindeverband and Gemeinde records all describe the same city. Bremen
+//! consists of two kreisfreie Staedte (Bremen and Bremerhaven).
+
+use super::{
+    gemeinde::{GemeindeDaten, GemeindeSchluessel},
+    gemeindeverband::{GemeindeverbandDaten, GemeindeverbandSchluessel},
+    kreis::{KreisDaten, KreisSchluessel},
+    land::{LandDaten, LandSchluessel},
+    name::Name,
+    statistics::Statistics,
+};
+
+/// Keys of the Laender that are city-states: Hamburg, Bremen and Berlin.
+pub const STADTSTAATEN: [LandSchluessel; 3] = [
+    LandSchluessel::new(2),
+    LandSchluessel::new(4),
+    LandSchluessel::new(11),
+];
+
+impl LandSchluessel {
+    /// Whether the Land is a city-state (Berlin, Hamburg or Bremen).
+    pub fn is_stadtstaat(&self) -> bool {
+        STADTSTAATEN.contains(self)
+    }
+}
+
+/// A city of a city-state, with all records that describe it.
+#[derive(Clone, Debug)]
+pub struct Stadt<'a> {
+    /// The Kreis, which is identical to the city.
+    pub kreis: Option<&'a KreisDaten>,
+
+    /// The Gemeindeverband, which is identical to the city.
+    pub gemeindeverband: Option<&'a GemeindeverbandDaten>,
+
+    /// The Gemeinde
+    pub gemeinde: &'a GemeindeDaten,
+}
+
+impl<'a> Stadt<'a> {
+    /// Name of the city
+    pub fn name(&self) -> &'a Name {
+        &self.gemeinde.name
+    }
+
+    pub fn kreis_schluessel(&self) -> KreisSchluessel {
+        self.gemeinde.schluessel.into()
+    }
+
+    pub fn gemeindeverband_schluessel(&self) -> GemeindeverbandSchluessel {
+        self.gemeinde.schluessel.into()
+    }
+
+    pub fn gemeinde_schluessel(&self) -> GemeindeSchluessel {
+        self.gemeinde.schluessel
+    }
+}
+
+/// A city-state as one logical unit, with all records that describe it.
+#[derive(Clone, Debug)]
+pub struct Stadtstaat<'a> {
+    /// The Land
+    pub land: &'a LandDaten,
+
+    /// The cities of the city-state. Berlin and Hamburg consist of one city,
+    /// Bremen of two.
+    pub staedte: Vec<Stadt<'a>>,
+}
+
+impl<'a> Stadtstaat<'a> {
+    pub fn schluessel(&self) -> LandSchluessel {
+        self.land.schluessel
+    }
+
+    /// Name of the city-state
+    pub fn name(&self) -> &'a Name {
+        &self.land.name
+    }
+
+    /// Whether Land, Kreis and Gemeinde are all the same city (Berlin and
+    /// Hamburg).
+    pub fn is_single_city(&self) -> bool {
+        self.staedte.len() == 1
+    }
+
+    /// Keys of all Kreise of the city-state.
+    pub fn kreis_schluessel(&self) -> impl Iterator<Item = KreisSchluessel> + '_ {
+        self.staedte.iter().map(Stadt::kreis_schluessel)
+    }
+
+    /// Keys of all Gemeinden of the city-state.
+    pub fn gemeinde_schluessel(&self) -> impl Iterator<Item = GemeindeSchluessel> + '_ {
+        self.staedte.iter().map(Stadt::gemeinde_schluessel)
+    }
+
+    /// Whether the unit with key `k` is part of this city-state.
+    pub fn contains<K: Into<LandSchluessel>>(&self, k: K) -> bool {
+        k.into() == self.schluessel()
+    }
+
+    /// Area and population of the city-state. Each city is counted once, even
+    /// though it's described by several records.
+    pub fn statistics(&self) -> Statistics {
+        self.staedte.iter().map(|stadt| stadt.gemeinde).sum()
+    }
+}
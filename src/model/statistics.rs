@@ -0,0 +1,212 @@
+//! Area, population and derived metrics (e.g. population density).
+
+use std::{
+    fmt::{self, Display, Formatter},
+    iter::Sum,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+use super::gemeinde::GemeindeDaten;
+
+/// Hectares per square kilometer.
+const HECTARES_PER_SQUARE_KILOMETER: f64 = 100.0;
+
+/// An area. GV100AD specifies areas in hectare (10000 square-meter).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Area {
+    hectares: u64,
+}
+
+impl Area {
+    pub fn from_hectares(hectares: u64) -> Self {
+        Self { hectares }
+    }
+
+    /// Returns the area in hectare.
+    pub fn hectares(&self) -> u64 {
+        self.hectares
+    }
+
+    /// Returns the area in square kilometers.
+    pub fn square_kilometers(&self) -> f64 {
+        self.hectares as f64 / HECTARES_PER_SQUARE_KILOMETER
+    }
+}
+
+impl Add for Area {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::from_hectares(self.hectares + rhs.hectares)
+    }
+}
+
+impl AddAssign for Area {
+    fn add_assign(&mut self, rhs: Self) {
+        self.hectares += rhs.hectares;
+    }
+}
+
+impl Sub for Area {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_hectares(self.hectares - rhs.hectares)
+    }
+}
+
+impl SubAssign for Area {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.hectares -= rhs.hectares;
+    }
+}
+
+impl Sum for Area {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl Display for Area {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} ha", self.hectares)
+    }
+}
+
+/// Area and population of a unit. For units above Gemeinde level, these are
+/// the sums over all Gemeinden in the unit.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Statistics {
+    /// Area
+    pub area: Area,
+
+    /// Total population
+    pub population_total: u64,
+
+    /// Male population
+    pub population_male: u64,
+
+    /// Number of Gemeinden. As in the official Destatis counts, this doesn't
+    /// include gemeindefreie Gebiete.
+    pub gemeinden: usize,
+
+    /// Number of gemeindefreie Gebiete
+    pub gemeindefreie_gebiete: usize,
+}
+
+impl Statistics {
+    /// Female population
+    pub fn population_female(&self) -> u64 {
+        self.population_total.saturating_sub(self.population_male)
+    }
+
+    /// Sex ratio as males per 100 females. Returns `None` if there are no
+    /// females.
+    pub fn sex_ratio(&self) -> Option<f64> {
+        let female = self.population_female();
+        if female == 0 {
+            None
+        } else {
+            Some(100.0 * self.population_male as f64 / female as f64)
+        }
+    }
+
+    /// Population density in inhabitants per square kilometer. Returns `None`
+    /// if the area is 0.
+    pub fn population_density(&self) -> Option<f64> {
+        if self.area.hectares() == 0 {
+            None
+        } else {
+            Some(self.population_total as f64 / self.area.square_kilometers())
+        }
+    }
+
+    /// Share of population compared to `parent` (e.g. the Land), as a fraction
+    /// between 0 and 1. Returns `None` if `parent` has no population.
+    pub fn population_share_of(&self, parent: &Statistics) -> Option<f64> {
+        if parent.population_total == 0 {
+            None
+        } else {
+            Some(self.population_total as f64 / parent.population_total as f64)
+        }
+    }
+
+    /// Share of area compared to `parent` (e.g. the Land), as a fraction
+    /// between 0 and 1. Returns `None` if `parent` has no area.
+    pub fn area_share_of(&self, parent: &Statistics) -> Option<f64> {
+        if parent.area.hectares() == 0 {
+            None
+        } else {
+            Some(self.area.hectares() as f64 / parent.area.hectares() as f64)
+        }
+    }
+}
+
+impl From<&GemeindeDaten> for Statistics {
+    fn from(gemeinde: &GemeindeDaten) -> Self {
+        Self {
+            area: gemeinde.area,
+            population_total: gemeinde.population_total,
+            population_male: gemeinde.population_male,
+            gemeinden: usize::from(!gemeinde.is_gemeindefrei()),
+            gemeindefreie_gebiete: usize::from(gemeinde.is_gemeindefrei()),
+        }
+    }
+}
+
+impl Add for Statistics {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign for Statistics {
+    fn add_assign(&mut self, rhs: Self) {
+        self.area += rhs.area;
+        self.population_total += rhs.population_total;
+        self.population_male += rhs.population_male;
+        self.gemeinden += rhs.gemeinden;
+        self.gemeindefreie_gebiete += rhs.gemeindefreie_gebiete;
+    }
+}
+
+impl Sub for Statistics {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        self -= rhs;
+        self
+    }
+}
+
+impl SubAssign for Statistics {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.area -= rhs.area;
+        self.population_total -= rhs.population_total;
+        self.population_male -= rhs.population_male;
+        self.gemeinden -= rhs.gemeinden;
+        self.gemeindefreie_gebiete -= rhs.gemeindefreie_gebiete;
+    }
+}
+
+impl Sum for Statistics {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a GemeindeDaten> for Statistics {
+    fn sum<I: Iterator<Item = &'a GemeindeDaten>>(iter: I) -> Self {
+        iter.map(Statistics::from).sum()
+    }
+}
+
+impl GemeindeDaten {
+    /// Returns area and population of the Gemeinde.
+    pub fn statistics(&self) -> Statistics {
+        self.into()
+    }
+}
@@ -0,0 +1,90 @@
+//! The body that effectively administers a Gemeinde. Depending on the type of
+//! Gemeindeverband, this is the Gemeindeverband (e.g. an Amt), a member
+//! Gemeinde that administers the others (erfüllende Gemeinde), or the Gemeinde
+//! itself.
+
+use super::{
+    gemeinde::GemeindeDaten,
+    gemeindeverband::{GemeindeverbandDaten, GemeindeverbandTextkennzeichen},
+    name::Name,
+};
+
+impl GemeindeverbandTextkennzeichen {
+    /// Whether a member Gemeinde administers the other Gemeinden of the
+    /// Gemeindeverband (erfüllende Gemeinde, or VG-Trägermodell).
+    pub fn is_administered_by_member(&self) -> bool {
+        matches!(self, Self::ErfuellendeGemeinde | Self::VGTraegermodell)
+    }
+}
+
+/// Body that effectively administers a Gemeinde.
+#[derive(Clone, Debug)]
+pub enum Verwaltung<'a> {
+    /// The Gemeinde is verbandsfrei and administers itself.
+    Selbst(&'a GemeindeDaten),
+
+    /// The Gemeindeverband (e.g. Amt, Samtgemeinde, Verbandsgemeinde)
+    /// administers its Gemeinden.
+    Gemeindeverband(&'a GemeindeverbandDaten),
+
+    /// A member Gemeinde administers all Gemeinden of the Gemeindeverband.
+    ErfuellendeGemeinde {
+        gemeindeverband: &'a GemeindeverbandDaten,
+        gemeinde: &'a GemeindeDaten,
+    },
+}
+
+impl<'a> Verwaltung<'a> {
+    /// Name of the administering body
+    pub fn name(&self) -> &'a Name {
+        match self {
+            Self::Selbst(gemeinde) => &gemeinde.name,
+            Self::Gemeindeverband(gemeindeverband) => &gemeindeverband.name,
+            Self::ErfuellendeGemeinde { gemeinde, .. } => &gemeinde.name,
+        }
+    }
+
+    /// Location of the administration. For Gemeinden that administer
+    /// themselves, this is the Gemeinde.
+    pub fn sitz_verwaltung(&self) -> Option<&'a str> {
+        match self {
+            Self::Selbst(gemeinde) => Some(gemeinde.name.german()),
+            Self::Gemeindeverband(gemeindeverband) => gemeindeverband.sitz_verwaltung.as_deref(),
+            Self::ErfuellendeGemeinde {
+                gemeindeverband,
+                gemeinde,
+            } => Some(
+                gemeindeverband
+                    .sitz_verwaltung
+                    .as_deref()
+                    .unwrap_or_else(|| gemeinde.name.german()),
+            ),
+        }
+    }
+
+    /// The Gemeindeverband, unless the Gemeinde administers itself.
+    pub fn gemeindeverband(&self) -> Option<&'a GemeindeverbandDaten> {
+        match self {
+            Self::Selbst(_) => None,
+            Self::Gemeindeverband(gemeindeverband) => Some(gemeindeverband),
+            Self::ErfuellendeGemeinde {
+                gemeindeverband, ..
+            } => Some(gemeindeverband),
+        }
+    }
+
+    /// The administering Gemeinde, if the body is a Gemeinde.
+    pub fn gemeinde(&self) -> Option<&'a GemeindeDaten> {
+        match self {
+            Self::Selbst(gemeinde) => Some(gemeinde),
+            Self::Gemeindeverband(_) => None,
+            Self::ErfuellendeGemeinde { gemeinde, .. } => Some(gemeinde),
+        }
+    }
+}
+
+/// Whether `gemeinde` is the one named by `name` (e.g. the `sitz_verwaltung`
+/// of its Gemeindeverband).
+pub(crate) fn is_named(gemeinde: &GemeindeDaten, name: &str) -> bool {
+    gemeinde.name.matches(name) || gemeinde.name.base == Name::parse(name).base
+}
@@ -0,0 +1,204 @@
+//! Electoral districts for the Bundestag (Bundestagswahlkreise). The numbering
+//! of the Wahlkreise changes between elections, so numbers are only meaningful
+//! together with a [`Wahlperiode`].
+
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+use crate::error::Error;
+
+use super::land::LandSchluessel;
+
+/// Years of the Bundestag elections, starting with the 1. Wahlperiode.
+const ELECTION_YEARS: [u16; 21] = [
+    1949, 1953, 1957, 1961, 1965, 1969, 1972, 1976, 1980, 1983, 1987, 1990, 1994, 1998, 2002, 2005,
+    2009, 2013, 2017, 2021, 2025,
+];
+
+/// Legislative period of the Bundestag (e.g. the 20. Wahlperiode, elected in
+/// 2021).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Wahlperiode {
+    pub wahlperiode: u8,
+}
+
+impl Wahlperiode {
+    pub fn new(wahlperiode: u8) -> Self {
+        Self { wahlperiode }
+    }
+
+    /// Returns the Wahlperiode that was elected in `year`, if there was a
+    /// Bundestag election in that year.
+    pub fn from_election_year(year: u16) -> Option<Self> {
+        let index = ELECTION_YEARS.iter().position(|y| *y == year)?;
+        Some(Self::new(index as u8 + 1))
+    }
+
+    /// Returns the year of the election of this Wahlperiode, if known.
+    pub fn election_year(&self) -> Option<u16> {
+        ELECTION_YEARS
+            .get(usize::from(self.wahlperiode).checked_sub(1)?)
+            .copied()
+    }
+}
+
+impl FromStr for Wahlperiode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s.parse()?))
+    }
+}
+
+impl Display for Wahlperiode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}. Wahlperiode", self.wahlperiode)
+    }
+}
+
+/// Associated election districts. If `Range`, it can include gaps, i.e.
+/// Wahlkreise that don't contain any part of the Gemeinde. Large cities are
+/// usually split over several Wahlkreise and have a `Range`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Bundestagswahlkreise {
+    Single(u16),
+    Range(u16, u16),
+}
+
+impl Bundestagswahlkreise {
+    /// Returns the first Wahlkreis.
+    pub fn first(&self) -> u16 {
+        match self {
+            Self::Single(n) => *n,
+            Self::Range(von, _) => *von,
+        }
+    }
+
+    /// Returns the last Wahlkreis.
+    pub fn last(&self) -> u16 {
+        match self {
+            Self::Single(n) => *n,
+            Self::Range(_, bis) => *bis,
+        }
+    }
+
+    /// Iterates over the numbers of all Wahlkreise. For a `Range`, this
+    /// includes possible gaps.
+    pub fn iter(&self) -> RangeInclusive<u16> {
+        self.first()..=self.last()
+    }
+
+    /// Whether Wahlkreis `nummer` is in this set. For a `Range`, this is also
+    /// true for possible gaps.
+    pub fn contains(&self, nummer: u16) -> bool {
+        self.iter().contains(&nummer)
+    }
+
+    /// Whether the Gemeinde is split over several Wahlkreise.
+    pub fn is_split(&self) -> bool {
+        matches!(self, Self::Range(..))
+    }
+}
+
+impl IntoIterator for Bundestagswahlkreise {
+    type Item = u16;
+    type IntoIter = RangeInclusive<u16>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromStr for Bundestagswahlkreise {
+    type Err = Error;
+
+    /// Parses the Wahlkreis field of a Gemeinde. This consists of the first
+    /// Wahlkreis (3 digits), and optionally the last Wahlkreis (3 digits). As
+    /// the field is the last one of the line, the padding of a missing last
+    /// Wahlkreis may be trimmed.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidBundestagswahlkreise(s.to_owned());
+
+        if !(3..=6).contains(&s.len()) || !s.is_char_boundary(3) {
+            return Err(invalid());
+        }
+
+        let von = s[..3].parse().map_err(|_| invalid())?;
+        tracing::trace!(von = ?von);
+
+        let bis = s[3..].trim();
+        tracing::trace!(bis = ?bis);
+        if bis.is_empty() {
+            Ok(Bundestagswahlkreise::Single(von))
+        } else {
+            let bis = bis.parse().map_err(|_| invalid())?;
+            if bis < von {
+                return Err(invalid());
+            }
+            Ok(Bundestagswahlkreise::Range(von, bis))
+        }
+    }
+}
+
+/// A Bundestagswahlkreis, as published by the Bundeswahlleiterin.
+#[derive(Clone, Debug)]
+pub struct Bundestagswahlkreis {
+    /// Wahlperiode to which the numbering belongs
+    pub wahlperiode: Wahlperiode,
+
+    /// Number of the Wahlkreis
+    pub nummer: u16,
+
+    /// Name of the Wahlkreis (e.g. `Saarbrücken`)
+    pub name: String,
+
+    /// Land of the Wahlkreis
+    pub land: Option<LandSchluessel>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_wahlkreise() {
+        let single = "296   ".parse::<Bundestagswahlkreise>().unwrap();
+        assert_eq!(single, Bundestagswahlkreise::Single(296));
+        assert!(!single.is_split());
+        assert_eq!(single.iter().collect::<Vec<_>>(), vec![296]);
+
+        let range = "075086".parse::<Bundestagswahlkreise>().unwrap();
+        assert_eq!(range, Bundestagswahlkreise::Range(75, 86));
+        assert!(range.is_split());
+        assert_eq!(range.iter().count(), 12);
+        assert!(range.contains(80));
+        assert!(!range.contains(87));
+
+        for s in ["296", "296 ", "296  "].iter() {
+            assert_eq!(
+                s.parse::<Bundestagswahlkreise>().unwrap(),
+                Bundestagswahlkreise::Single(296)
+            );
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_wahlkreise() {
+        assert!("29".parse::<Bundestagswahlkreise>().is_err());
+        assert!("086075".parse::<Bundestagswahlkreise>().is_err());
+        assert!("2a6   ".parse::<Bundestagswahlkreise>().is_err());
+    }
+
+    #[test]
+    fn it_maps_election_years() {
+        let wahlperiode = Wahlperiode::from_election_year(2021).unwrap();
+        assert_eq!(wahlperiode, Wahlperiode::new(20));
+        assert_eq!(wahlperiode.election_year(), Some(2021));
+        assert_eq!(wahlperiode.to_string(), "20. Wahlperiode");
+        assert_eq!(Wahlperiode::from_election_year(2020), None);
+        assert_eq!(Wahlperiode::new(0).election_year(), None);
+    }
+}
@@ -14,10 +14,13 @@ use crate::{
         datensatz::Datensatz,
         gemeinde::{GemeindeDaten, RegionalSchluessel},
         gemeindeverband::{GemeindeverbandDaten, GemeindeverbandSchluessel},
-        kreis::KreisDaten,
+        gericht::Gerichtbarkeit,
+        kreis::{KreisDaten, KreisSchluessel},
         land::LandDaten,
+        name::Name,
         regierungsbezirk::RegierungsbezirkDaten,
         region::RegionDaten,
+        statistics::Area,
     },
 };
 
@@ -148,7 +151,7 @@ impl<R: BufRead> Parser<R> {
 
                 fields.skip(10);
 
-                let name = fields.next(50).trim().to_owned();
+                let name = Name::parse(fields.next(50));
 
                 let sitz_regierung = fields.next(50).trim().to_owned();
 
@@ -169,7 +172,7 @@ impl<R: BufRead> Parser<R> {
 
                 fields.skip(9);
 
-                let name = fields.next(50).trim().to_owned();
+                let name = Name::parse(fields.next(50));
 
                 let sitz_verwaltung = fields.next(50).trim().to_owned();
 
@@ -189,7 +192,7 @@ impl<R: BufRead> Parser<R> {
                 let schluessel = fields.parse_next(4)?;
                 tracing::debug!(schluessel = ?schluessel);
 
-                let name = fields.next(50).trim().to_owned();
+                let name = Name::parse(fields.next(50));
                 tracing::debug!(name = ?name);
 
                 let sitz_verwaltung = fields.next(50).trim().to_owned();
@@ -208,12 +211,12 @@ impl<R: BufRead> Parser<R> {
                 let gebietsstand = parse_date(fields.next(8))?;
                 tracing::debug!(gebietsstand = ?gebietsstand);
 
-                let schluessel = fields.parse_next(5)?;
+                let schluessel = fields.parse_next::<KreisSchluessel>(5)?;
                 tracing::debug!(schluessel = ?schluessel);
 
                 fields.skip(7);
 
-                let name = fields.next(50).trim().to_owned();
+                let name = Name::parse_in_land(fields.next(50), schluessel.into());
                 tracing::debug!(name = ?name);
 
                 let sitz_verwaltung = fields.next(50).trim().to_owned();
@@ -246,7 +249,7 @@ impl<R: BufRead> Parser<R> {
 
                 let schluessel = GemeindeverbandSchluessel::new(kreis_schluessel, gemeindeverband);
 
-                let name = fields.next(50).trim().to_owned();
+                let name = Name::parse_in_land(fields.next(50), schluessel.into());
                 tracing::debug!(name = ?name);
 
                 let sitz_verwaltung = fields.next_opt(50).map(|s| s.trim().to_owned());
@@ -277,7 +280,7 @@ impl<R: BufRead> Parser<R> {
 
                 let schluessel = regional_schluessel.to_gemeinde_schluessel(gemeindeverband);
 
-                let name = fields.next(50).trim().to_owned();
+                let name = Name::parse_in_land(fields.next(50), schluessel.into());
                 tracing::debug!(name = ?name);
 
                 fields.skip(50);
@@ -287,7 +290,7 @@ impl<R: BufRead> Parser<R> {
 
                 fields.skip(4);
 
-                let area = fields.parse_next(11)?;
+                let area = Area::from_hectares(fields.parse_next(11)?);
                 tracing::debug!(area = ?area);
 
                 let population_total = fields.parse_next(11)?;
@@ -298,7 +301,7 @@ impl<R: BufRead> Parser<R> {
 
                 fields.skip(4);
 
-                let plz = fields.next(5).to_owned();
+                let plz = fields.parse_next_opt(5)?;
                 tracing::debug!(plz = ?plz);
 
                 let plz_unambiguous = fields.next_opt(5).is_none();
@@ -309,7 +312,10 @@ impl<R: BufRead> Parser<R> {
                 let finanzamtbezirk = fields.parse_next_opt(4)?;
                 tracing::debug!(finanzamtbezirk = ?finanzamtbezirk);
 
-                let gerichtbarkeit = fields.parse_next_opt(4)?;
+                let gerichtbarkeit = fields
+                    .next_opt(4)
+                    .map(|s| Gerichtbarkeit::parse(s, schluessel.into()))
+                    .transpose()?;
                 tracing::debug!(gerichtbarkeit = ?gerichtbarkeit);
 
                 let arbeitsargenturbezirk = fields.parse_next_opt(5)?;
@@ -330,6 +336,7 @@ impl<R: BufRead> Parser<R> {
                     population_male,
                     plz,
                     plz_unambiguous,
+                    all_plz: plz.into_iter().collect(),
                     finanzamtbezirk,
                     gerichtbarkeit,
                     arbeitsargenturbezirk,
@@ -348,29 +355,40 @@ impl<R: BufRead> Parser<R> {
 /// Parses date from a field. This is just year, month, day without any
 /// seperators. German timezones apply.
 pub fn parse_date(s: &str) -> Result<NaiveDate, Error> {
-    Ok(NaiveDate::from_ymd(
-        s[0..4].parse()?,
-        s[4..6].parse()?,
-        s[6..8].parse()?,
-    ))
+    NaiveDate::from_ymd_opt(s[0..4].parse()?, s[4..6].parse()?, s[6..8].parse()?)
+        .ok_or_else(|| Error::InvalidDate(s.to_owned()))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::io::Cursor;
+    use std::{convert::TryFrom, fmt::Debug, io::Cursor};
 
     use crate::model::{
         datensatz::Datensatz,
-        gemeinde::{Bundestagswahlkreise, GemeindeSchluessel, GemeindeTextkennzeichen},
+        gemeinde::{GemeindeSchluessel, GemeindeTextkennzeichen},
         gemeindeverband::{GemeindeverbandSchluessel, GemeindeverbandTextkennzeichen},
         kreis::{KreisSchluessel, KreisTextkennzeichen},
         land::LandSchluessel,
         regierungsbezirk::RegierungsbezirkSchluessel,
         region::RegionSchluessel,
+        wahlkreis::Bundestagswahlkreise,
     };
 
     use super::*;
 
+    /// Asserts that every variant maps back to itself from its code, and that
+    /// no other code is accepted.
+    fn assert_codes_round_trip<T>(variants: &[T], code: fn(&T) -> u8)
+    where
+        T: Copy + Debug + PartialEq + TryFrom<u8>,
+    {
+        for t in variants {
+            assert_eq!(T::try_from(code(t)).ok(), Some(*t));
+        }
+        let accepted = (0..=u8::MAX).filter(|n| T::try_from(*n).is_ok()).count();
+        assert_eq!(accepted, variants.len());
+    }
+
     fn parse_single_line(line: &str) -> Datensatz {
         let mut parser = Parser::new(Cursor::new(line));
         let record = parser.parse_line().unwrap().unwrap();
@@ -385,7 +403,10 @@ mod tests {
 
         match record {
             Datensatz::Land(land) => {
-                assert_eq!(land.gebietsstand, NaiveDate::from_ymd(2021, 04, 30));
+                assert_eq!(
+                    land.gebietsstand,
+                    NaiveDate::from_ymd_opt(2021, 4, 30).unwrap()
+                );
                 assert_eq!(land.schluessel, LandSchluessel::new(10));
                 assert_eq!(land.name, "Saarland");
                 assert_eq!(land.sitz_regierung, "Saarbrücken, Landeshauptstadt");
@@ -403,13 +424,15 @@ mod tests {
             Datensatz::Regierungsbezirk(regierungsbezirk) => {
                 assert_eq!(
                     regierungsbezirk.gebietsstand,
-                    NaiveDate::from_ymd(2021, 04, 30)
+                    NaiveDate::from_ymd_opt(2021, 4, 30).unwrap()
                 );
                 assert_eq!(
                     regierungsbezirk.schluessel,
                     RegierungsbezirkSchluessel::new(LandSchluessel::new(7), 2)
                 );
                 assert_eq!(regierungsbezirk.name, "früher: Reg.-Bez. Trier");
+                assert_eq!(regierungsbezirk.name.base, "Trier");
+                assert!(regierungsbezirk.name.former);
                 assert_eq!(regierungsbezirk.sitz_verwaltung, "Trier, Stadt");
             }
             _ => panic!("Incorrect record type"),
@@ -423,7 +446,10 @@ mod tests {
 
         match record {
             Datensatz::Region(region) => {
-                assert_eq!(region.gebietsstand, NaiveDate::from_ymd(2021, 04, 30));
+                assert_eq!(
+                    region.gebietsstand,
+                    NaiveDate::from_ymd_opt(2021, 4, 30).unwrap()
+                );
                 assert_eq!(
                     region.schluessel,
                     RegionSchluessel::new(
@@ -445,7 +471,10 @@ mod tests {
 
         match record {
             Datensatz::Kreis(kreis) => {
-                assert_eq!(kreis.gebietsstand, NaiveDate::from_ymd(2021, 04, 30));
+                assert_eq!(
+                    kreis.gebietsstand,
+                    NaiveDate::from_ymd_opt(2021, 4, 30).unwrap()
+                );
                 assert_eq!(
                     kreis.schluessel,
                     KreisSchluessel::new_land(LandSchluessel::new(10), 41)
@@ -453,6 +482,8 @@ mod tests {
                 assert_eq!(kreis.name, "Regionalverband Saarbrücken");
                 assert_eq!(kreis.sitz_verwaltung, "Saarbrücken, Landeshauptstadt");
                 assert_eq!(kreis.textkennzeichen, KreisTextkennzeichen::Regionalverband);
+                assert_eq!(kreis.textkennzeichen.code(), 45);
+                assert!(!kreis.textkennzeichen.is_city());
             }
             _ => panic!("Incorrect record type"),
         }
@@ -467,7 +498,7 @@ mod tests {
             Datensatz::Gemeindeverband(gemeindeverband) => {
                 assert_eq!(
                     gemeindeverband.gebietsstand,
-                    NaiveDate::from_ymd(2021, 04, 30)
+                    NaiveDate::from_ymd_opt(2021, 4, 30).unwrap()
                 );
                 assert_eq!(
                     gemeindeverband.schluessel,
@@ -494,7 +525,10 @@ mod tests {
 
         match record {
             Datensatz::Gemeinde(gemeinde) => {
-                assert_eq!(gemeinde.gebietsstand, NaiveDate::from_ymd(2021, 04, 30));
+                assert_eq!(
+                    gemeinde.gebietsstand,
+                    NaiveDate::from_ymd_opt(2021, 4, 30).unwrap()
+                );
                 assert_eq!(
                     gemeinde.schluessel,
                     GemeindeSchluessel::new(
@@ -507,16 +541,19 @@ mod tests {
                 );
                 assert_eq!(gemeinde.name, "Saarbrücken, Landeshauptstadt");
                 assert_eq!(gemeinde.textkennzeichen, GemeindeTextkennzeichen::Stadt);
-                assert_eq!(gemeinde.area, 16752);
+                assert_eq!(gemeinde.textkennzeichen.code(), 63);
+                assert_eq!(gemeinde.textkennzeichen.label(), "Stadt");
+                assert!(gemeinde.textkennzeichen.is_city());
+                assert_eq!(gemeinde.area.hectares(), 16752);
                 assert_eq!(gemeinde.population_total, 180374);
                 assert_eq!(gemeinde.population_male, 89528);
-                assert_eq!(gemeinde.plz, "66111");
-                assert_eq!(gemeinde.plz_unambiguous, false);
+                assert_eq!(gemeinde.plz, Some("66111".parse().unwrap()));
+                assert!(!gemeinde.plz_unambiguous);
                 assert_eq!(gemeinde.finanzamtbezirk, Some(1040));
                 let gerichtbarkeit = gemeinde.gerichtbarkeit.unwrap();
-                assert_eq!(gerichtbarkeit.oberlandesgericht, "1");
-                assert_eq!(gerichtbarkeit.landgericht, "1");
-                assert_eq!(gerichtbarkeit.amtsgericht, "09");
+                assert_eq!(gerichtbarkeit.oberlandesgericht.to_string(), "101");
+                assert_eq!(gerichtbarkeit.landgericht.to_string(), "1011");
+                assert_eq!(gerichtbarkeit.amtsgericht.to_string(), "101109");
                 assert_eq!(gemeinde.arbeitsargenturbezirk, Some(55501));
                 match gemeinde.bundestagswahlkreise {
                     Some(Bundestagswahlkreise::Single(n)) => assert_eq!(n, 296),
@@ -526,4 +563,45 @@ mod tests {
             _ => panic!("Incorrect record type"),
         }
     }
+
+    #[test]
+    fn textkennzeichen_codes_round_trip() {
+        assert_codes_round_trip(
+            &[
+                KreisTextkennzeichen::KreisfreieStadt,
+                KreisTextkennzeichen::Stadtkreis,
+                KreisTextkennzeichen::Kreis,
+                KreisTextkennzeichen::Landkreis,
+                KreisTextkennzeichen::Regionalverband,
+            ],
+            KreisTextkennzeichen::code,
+        );
+        assert_codes_round_trip(
+            &[
+                GemeindeverbandTextkennzeichen::VerbandsfreieGemeinde,
+                GemeindeverbandTextkennzeichen::Amt,
+                GemeindeverbandTextkennzeichen::Samtgemeinde,
+                GemeindeverbandTextkennzeichen::Verbandsgemeinde,
+                GemeindeverbandTextkennzeichen::Verwaltungsgemeinschaft,
+                GemeindeverbandTextkennzeichen::Kirchspielslandgemeinde,
+                GemeindeverbandTextkennzeichen::Verwaltungsverband,
+                GemeindeverbandTextkennzeichen::VGTraegermodell,
+                GemeindeverbandTextkennzeichen::ErfuellendeGemeinde,
+            ],
+            GemeindeverbandTextkennzeichen::code,
+        );
+        assert_codes_round_trip(
+            &[
+                GemeindeTextkennzeichen::Markt,
+                GemeindeTextkennzeichen::KreisfreieStadt,
+                GemeindeTextkennzeichen::Stadtkreis,
+                GemeindeTextkennzeichen::Stadt,
+                GemeindeTextkennzeichen::KreisangehoerigeGemeinde,
+                GemeindeTextkennzeichen::GemeindefreiesGebietBewohnt,
+                GemeindeTextkennzeichen::GemeindefreiesGebietUnbewohnt,
+                GemeindeTextkennzeichen::GrosseKreisstadt,
+            ],
+            GemeindeTextkennzeichen::code,
+        );
+    }
 }
@@ -0,0 +1,404 @@
+//! Composable queries over the units in a [`Database`], e.g. all Städte in
+//! Bayern with more than 20,000 inhabitants, sorted by population density:
+//!
+//! ```rust,no_run
+//! use gv100ad::{
+//!     land,
+//!     model::record::{Ebene, Record},
+//!     query::SortBy,
+//!     Database,
+//! };
+//!
+//! let db = Database::from_path("GV100AD3004/GV100AD_300421.txt").unwrap();
+//!
+//! let staedte = db
+//!     .query()
+//!     .ebene(Ebene::Gemeinde)
+//!     .within(land!("09"))
+//!     .staedte()
+//!     .population(20_000..)
+//!     .sort_by(SortBy::Density)
+//!     .descending()
+//!     .limit(10)
+//!     .rows();
+//!
+//! for row in staedte {
+//!     println!("{}: {:?}", row.datensatz.name(), row.statistics.population_density());
+//! }
+//! ```
+//!
+//! Candidates are taken from the key range of the unit given to
+//! [`Query::within`], so queries within a Land or Kreis don't scan all units.
+//! Area and population are taken from the cached totals (see
+//! [`Database::totals`]).
+
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{
+    db::Database,
+    model::{
+        bezirk::Bezirk,
+        datensatz::{DatensatzRef, Schluessel},
+        gemeinde::GemeindeDaten,
+        gemeindefrei::GemeindefreiFilter,
+        gemeindeverband::GemeindeverbandDaten,
+        kreis::KreisDaten,
+        land::LandDaten,
+        record::{Ebene, Record, Textkennzeichen},
+        regierungsbezirk::RegierungsbezirkDaten,
+        region::{RegionDaten, RegionSchluessel},
+        search::{self, NamePattern},
+        statistics::Statistics,
+    },
+};
+
+/// Order of the results of a [`Query`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SortBy {
+    /// By key (default)
+    Schluessel,
+
+    /// By folded name (see [`search::fold`])
+    Name,
+
+    /// By total population
+    Population,
+
+    /// By area
+    Area,
+
+    /// By population density. Units without area come first.
+    Density,
+}
+
+/// A unit matched by a [`Query`], with its area and population.
+#[derive(Copy, Clone, Debug)]
+pub struct QueryRow<'a> {
+    /// The record of the unit
+    pub datensatz: DatensatzRef<'a>,
+
+    /// Area and population of the unit
+    pub statistics: Statistics,
+}
+
+/// A query over the units of one level. Created by [`Database::query`].
+///
+/// All filters must match. By default, all Gemeinden (including gemeindefreie
+/// Gebiete) are returned, ordered by key.
+#[derive(Clone, Debug)]
+pub struct Query<'a> {
+    db: &'a Database,
+    ebene: Ebene,
+    within: Option<Schluessel>,
+    textkennzeichen: Vec<Textkennzeichen>,
+    staedte: bool,
+    gemeindefrei: GemeindefreiFilter,
+    population: (Bound<u64>, Bound<u64>),
+    area: (Bound<u64>, Bound<u64>),
+    density: (Bound<f64>, Bound<f64>),
+    name: Option<NamePattern>,
+    bezirke: Vec<Bezirk>,
+    sort_by: SortBy,
+    descending: bool,
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl<'a> Query<'a> {
+    pub(crate) fn new(db: &'a Database) -> Self {
+        Self {
+            db,
+            ebene: Ebene::Gemeinde,
+            within: None,
+            textkennzeichen: vec![],
+            staedte: false,
+            gemeindefrei: GemeindefreiFilter::default(),
+            population: (Bound::Unbounded, Bound::Unbounded),
+            area: (Bound::Unbounded, Bound::Unbounded),
+            density: (Bound::Unbounded, Bound::Unbounded),
+            name: None,
+            bezirke: vec![],
+            sort_by: SortBy::Schluessel,
+            descending: false,
+            offset: 0,
+            limit: None,
+        }
+    }
+
+    /// Only return units of this level. Defaults to Gemeinden.
+    pub fn ebene(mut self, ebene: Ebene) -> Self {
+        self.ebene = ebene;
+        self
+    }
+
+    /// Only return units that lie within the unit with key `k` (e.g. a Land).
+    pub fn within<K: Into<Schluessel>>(mut self, k: K) -> Self {
+        self.within = Some(k.into());
+        self
+    }
+
+    /// Only return units with this Textkennzeichen. If called several times,
+    /// units with any of the Textkennzeichen are returned. Laender,
+    /// Regierungsbezirke and Regionen have no Textkennzeichen and never match.
+    pub fn textkennzeichen<T: Into<Textkennzeichen>>(mut self, textkennzeichen: T) -> Self {
+        self.textkennzeichen.push(textkennzeichen.into());
+        self
+    }
+
+    /// Only return cities, i.e. Kreise or Gemeinden with a Textkennzeichen
+    /// that denotes a city (see [`Textkennzeichen::is_city`]).
+    pub fn staedte(mut self) -> Self {
+        self.staedte = true;
+        self
+    }
+
+    /// Whether to return gemeindefreie Gebiete. Only applies to Gemeinden.
+    pub fn gemeindefrei(mut self, filter: GemeindefreiFilter) -> Self {
+        self.gemeindefrei = filter;
+        self
+    }
+
+    /// Only return units whose total population is in `range`.
+    pub fn population<R: RangeBounds<u64>>(mut self, range: R) -> Self {
+        self.population = (range.start_bound().cloned(), range.end_bound().cloned());
+        self
+    }
+
+    /// Only return units whose area in hectare is in `range`.
+    pub fn area<R: RangeBounds<u64>>(mut self, range: R) -> Self {
+        self.area = (range.start_bound().cloned(), range.end_bound().cloned());
+        self
+    }
+
+    /// Only return units whose population density in inhabitants per square
+    /// kilometer is in `range`. Units without area never match.
+    pub fn density<R: RangeBounds<f64>>(mut self, range: R) -> Self {
+        self.density = (range.start_bound().cloned(), range.end_bound().cloned());
+        self
+    }
+
+    /// Only return units whose name matches `pattern`, with `*` as wildcard
+    /// (e.g. `Neustadt*`). See [`NamePattern`].
+    pub fn name(mut self, pattern: &str) -> Self {
+        self.name = Some(NamePattern::new(pattern));
+        self
+    }
+
+    /// Only return units with at least one Gemeinde in the functional district
+    /// `bezirk` (e.g. a Finanzamtbezirk). If called several times, units must
+    /// match all districts.
+    pub fn bezirk<B: Into<Bezirk>>(mut self, bezirk: B) -> Self {
+        self.bezirke.push(bezirk.into());
+        self
+    }
+
+    /// Order the results by `sort_by`, ascending unless
+    /// [`Query::descending`] is called.
+    pub fn sort_by(mut self, sort_by: SortBy) -> Self {
+        self.sort_by = sort_by;
+        self
+    }
+
+    /// Order the results in descending order. Units that are equal in the
+    /// sort order are still ordered by ascending key.
+    pub fn descending(mut self) -> Self {
+        self.descending = true;
+        self
+    }
+
+    /// Skip the first `offset` results.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Return at most `limit` results.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Runs the query and returns the matching units with their area and
+    /// population.
+    pub fn rows(&self) -> Vec<QueryRow<'a>> {
+        let bezirke = self
+            .bezirke
+            .iter()
+            .map(|bezirk| self.units_in_bezirk(*bezirk))
+            .collect::<Vec<_>>();
+
+        let mut rows = self
+            .candidates()
+            .filter(|datensatz| {
+                let schluessel = datensatz.schluessel();
+                self.within
+                    .map_or(true, |within| within.contains(schluessel))
+                    && bezirke.iter().all(|units| units.contains(&schluessel))
+                    && self.matches_record(datensatz)
+            })
+            .map(|datensatz| QueryRow {
+                datensatz,
+                statistics: self.db.totals(datensatz.schluessel()).unwrap_or_default(),
+            })
+            .filter(|row| self.matches_statistics(&row.statistics))
+            .collect::<Vec<_>>();
+
+        rows.sort_by(|a, b| {
+            let ordering = self.compare(a, b);
+            let ordering = if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            ordering.then_with(|| a.datensatz.schluessel().cmp(&b.datensatz.schluessel()))
+        });
+
+        rows.into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    /// Runs the query and returns the records of the matching units.
+    pub fn records(&self) -> Vec<DatensatzRef<'a>> {
+        self.rows().into_iter().map(|row| row.datensatz).collect()
+    }
+
+    /// Runs the query and returns the keys of the matching units.
+    pub fn keys(&self) -> Vec<Schluessel> {
+        self.rows()
+            .into_iter()
+            .map(|row| row.datensatz.schluessel())
+            .collect()
+    }
+
+    /// Runs the query and returns the number of matching units. Offset and
+    /// limit are applied.
+    pub fn count(&self) -> usize {
+        self.rows().len()
+    }
+
+    /// Returns the units of the queried level that can match. If the query is
+    /// restricted to a unit, this is a key range of the BTreeMap of the level.
+    /// If the unit is below the queried level, the range only contains the
+    /// unit it belongs to, so the candidates still need to be filtered.
+    fn candidates(&self) -> Box<dyn Iterator<Item = DatensatzRef<'a>> + 'a> {
+        let db = self.db;
+
+        match (self.ebene, self.within) {
+            (Ebene::Land, _) => Box::new(db.all::<LandDaten>().map(DatensatzRef::Land)),
+            (Ebene::Regierungsbezirk, Some(within)) => Box::new(
+                db.children::<_, RegierungsbezirkDaten>(within)
+                    .map(DatensatzRef::Regierungsbezirk),
+            ),
+            (Ebene::Regierungsbezirk, None) => Box::new(
+                db.all::<RegierungsbezirkDaten>()
+                    .map(DatensatzRef::Regierungsbezirk),
+            ),
+            (Ebene::Region, Some(within)) => Box::new(
+                db.children::<_, RegionDaten>(within)
+                    .map(DatensatzRef::Region),
+            ),
+            (Ebene::Region, None) => Box::new(db.all::<RegionDaten>().map(DatensatzRef::Region)),
+            (Ebene::Kreis, Some(within)) => Box::new(
+                db.children::<_, KreisDaten>(within)
+                    .map(DatensatzRef::Kreis),
+            ),
+            (Ebene::Kreis, None) => Box::new(db.all::<KreisDaten>().map(DatensatzRef::Kreis)),
+            (Ebene::Gemeindeverband, Some(within)) => Box::new(
+                db.children::<_, GemeindeverbandDaten>(within)
+                    .map(DatensatzRef::Gemeindeverband),
+            ),
+            (Ebene::Gemeindeverband, None) => Box::new(
+                db.all::<GemeindeverbandDaten>()
+                    .map(DatensatzRef::Gemeindeverband),
+            ),
+            (Ebene::Gemeinde, Some(within)) => Box::new(
+                db.children::<_, GemeindeDaten>(within)
+                    .map(DatensatzRef::Gemeinde),
+            ),
+            (Ebene::Gemeinde, None) => {
+                Box::new(db.all::<GemeindeDaten>().map(DatensatzRef::Gemeinde))
+            }
+        }
+    }
+
+    /// Returns the keys of all units that contain a Gemeinde in `bezirk`.
+    fn units_in_bezirk(&self, bezirk: Bezirk) -> HashSet<Schluessel> {
+        self.db
+            .gemeinden_in_bezirk(bezirk)
+            .flat_map(|gemeinde| {
+                let schluessel = Schluessel::from(gemeinde.schluessel);
+                vec![
+                    Some(schluessel.land().into()),
+                    schluessel.regierungsbezirk().map(Schluessel::from),
+                    schluessel
+                        .kreis()
+                        .map(|kreis| RegionSchluessel::of_kreis(kreis).into()),
+                    schluessel.kreis().map(Schluessel::from),
+                    schluessel.gemeindeverband().map(Schluessel::from),
+                    Some(schluessel),
+                ]
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn matches_record(&self, datensatz: &DatensatzRef<'a>) -> bool {
+        if let DatensatzRef::Gemeinde(gemeinde) = datensatz {
+            if !self.gemeindefrei.matches(gemeinde) {
+                return false;
+            }
+        }
+
+        let textkennzeichen = datensatz.textkennzeichen();
+        if !self.textkennzeichen.is_empty()
+            && !textkennzeichen
+                .is_some_and(|textkennzeichen| self.textkennzeichen.contains(&textkennzeichen))
+        {
+            return false;
+        }
+        if self.staedte && !textkennzeichen.is_some_and(|textkennzeichen| textkennzeichen.is_city())
+        {
+            return false;
+        }
+
+        self.name
+            .as_ref()
+            .map_or(true, |pattern| pattern.matches(Record::name(datensatz)))
+    }
+
+    fn matches_statistics(&self, statistics: &Statistics) -> bool {
+        let density_unbounded = self.density == (Bound::Unbounded, Bound::Unbounded);
+
+        self.population.contains(&statistics.population_total)
+            && self.area.contains(&statistics.area.hectares())
+            && (density_unbounded
+                || statistics
+                    .population_density()
+                    .is_some_and(|density| self.density.contains(&density)))
+    }
+
+    fn compare(&self, a: &QueryRow<'a>, b: &QueryRow<'a>) -> Ordering {
+        match self.sort_by {
+            SortBy::Schluessel => a.datensatz.schluessel().cmp(&b.datensatz.schluessel()),
+            SortBy::Name => search::fold(a.datensatz.name()).cmp(&search::fold(b.datensatz.name())),
+            SortBy::Population => a
+                .statistics
+                .population_total
+                .cmp(&b.statistics.population_total),
+            SortBy::Area => a.statistics.area.cmp(&b.statistics.area),
+            SortBy::Density => {
+                let density = |row: &QueryRow| row.statistics.population_density();
+                match (density(a), density(b)) {
+                    (Some(a), Some(b)) => a.total_cmp(&b),
+                    (a, b) => a.is_some().cmp(&b.is_some()),
+                }
+            }
+        }
+    }
+}